@@ -0,0 +1,29 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform vec3 overlay_color;
+uniform float overlay_alpha;
+// Breaking progress from 0 to 1 for the crack overlay, or below 0 to fill with overlay_color
+uniform float crack_progress;
+
+out vec4 color;
+
+float hash(vec2 p) {
+    return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
+}
+
+void main() {
+    if (crack_progress < 0.0) {
+        color = vec4(overlay_color, overlay_alpha);
+        return;
+    }
+
+    // Faces are split into 8x8 cells which crack in a fixed random order, so the cracks spread as
+    // the block is broken instead of flickering. Texture coordinates span the face.
+    vec2 cell = clamp(floor(v_tex_coords * 8.0), 0.0, 7.0);
+    if (hash(cell) > crack_progress) { discard; }
+    color = vec4(overlay_color, overlay_alpha);
+}
@@ -9,6 +9,8 @@ flat in float v_type;
 in float v_lighting;
 
 uniform float time;
+// Dims the sunlight stored in the chunk at night
+uniform float sky_light;
 uniform sampler2D texture_map;
 
 layout (location = 0) out vec4 position;
@@ -19,7 +21,7 @@ void main() {
     vec4 color = texture(texture_map, v_tex_coords).rgba;
     if (color.a < 0.5) { discard; }
 
-    float lighting = min((v_lighting / 16.0) + (1./16.), 1.0);
+    float lighting = min((v_lighting * sky_light / 16.0) + (1./16.), 1.0);
     lighting = lighting * lighting * lighting;
     vec3 out_color = 0.5 * lighting * color.rgb + 0.5 * color.rgb;
 
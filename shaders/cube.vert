@@ -7,11 +7,17 @@ layout (location = 1) in vec3 normal;
 layout (location = 2) in vec2 tex_coords;
 layout (location = 3) in float vtype;
 layout (location = 4) in float lighting;
+layout (location = 5) in float animation;
+
+// Must match MAX_ANIMATIONS in atlas.rs
+#define MAX_ANIMATIONS 32
 
 uniform mat4 model_matrix;
 uniform mat4 view_matrix;
 uniform mat4 perspective_matrix;
 uniform float time;
+// Frame count, seconds per frame and distance in v between frames of each animated texture
+uniform vec3 animations[MAX_ANIMATIONS];
 
 out vec3 v_position;
 out vec3 v_normal;
@@ -34,7 +40,12 @@ void main() {
     vec4 position_viewspace = view_matrix * position_worldspace;
     v_position = position_viewspace.xyz;
     v_normal = normal;
+    // Animated textures step down their strip of frames in the atlas
     v_tex_coords = tex_coords;
+    if(animation > 0.0) {
+        vec3 frames = animations[int(animation) - 1];
+        v_tex_coords.y -= mod(floor(time / frames.y), frames.x) * frames.z;
+    }
     v_type = vtype;
     v_lighting = lighting;
 
@@ -0,0 +1,20 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_position;
+in vec2 v_tex_coords;
+
+uniform sampler2D texture_map;
+// 1 to multiply the color by texture_map, 0 to draw it flat
+uniform float textured;
+uniform vec3 color;
+uniform float alpha;
+
+out vec4 frag_color;
+
+void main() {
+    vec4 texel = textured > 0.5 ? texture(texture_map, v_tex_coords) : vec4(1.0);
+    if (texel.a < 0.01) { discard; }
+    frag_color = texel * vec4(color, alpha);
+}
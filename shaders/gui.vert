@@ -2,8 +2,8 @@
 
 precision mediump float;
 
-in vec2 position;
-in vec2 tex_coords;
+layout (location = 0) in vec2 position;
+layout (location = 1) in vec2 tex_coords;
 
 uniform mat3 model_matrix;
 
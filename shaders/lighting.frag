@@ -10,9 +10,64 @@ layout (location = 2) uniform sampler2D position;
 layout (location = 3) uniform sampler2D normal;
 
 uniform vec2 resolution;
+// 0.0 while SSAO is off, when nothing is bound to the ssao sampler
+uniform float ssao_strength;
+
+// Sun or moon, whichever is above the horizon
+uniform vec3 light_dir;
+uniform vec3 light_color;
+// Daylight left in the sky, from about 0.2 at midnight to 1.0 at noon
+uniform float sky_light;
+uniform vec3 fog_color;
+// View distances where fog starts and where it hides the terrain completely
+uniform float fog_start;
+uniform float fog_end;
+
+// One depth map per shadow cascade, GLES 3.1 can't index an array of samplers with a variable
+uniform highp sampler2D shadow_map_0;
+uniform highp sampler2D shadow_map_1;
+uniform highp sampler2D shadow_map_2;
+uniform highp mat4 light_matrices[3];
+// View distance each cascade covers
+uniform float cascade_ends[3];
+// Cascades in use, 0.0 while shadows are off
+uniform float shadow_cascades;
+uniform highp float shadow_texel_size;
+uniform highp mat4 inverse_view_matrix;
+
+#define SHADOW_BIAS 0.0015
 
 out vec4 color;
 
+highp float shadow_depth(int cascade, highp vec2 uv) {
+    if (cascade == 0) { return texture(shadow_map_0, uv).r; }
+    if (cascade == 1) { return texture(shadow_map_1, uv).r; }
+    return texture(shadow_map_2, uv).r;
+}
+
+// Fraction of the sun's light reaching a point, averaged over 3x3 shadow map texels
+float sun_visibility(highp vec3 world_position, vec3 world_normal, float view_distance) {
+    for (int cascade = 0; cascade < 3; ++cascade) {
+        if (float(cascade) >= shadow_cascades) { break; }
+        if (view_distance > cascade_ends[cascade]) { continue; }
+
+        // Push the point off its face a little so it doesn't shadow itself
+        highp vec4 light_space = light_matrices[cascade] * vec4(world_position + world_normal * 0.05, 1.0);
+        highp vec3 coords = light_space.xyz / light_space.w * 0.5 + 0.5;
+        if (coords.z > 1.0) { return 1.0; }
+
+        float lit = 0.0;
+        for (int x = -1; x <= 1; ++x) {
+            for (int y = -1; y <= 1; ++y) {
+                highp vec2 offset = vec2(float(x), float(y)) * shadow_texel_size;
+                lit += coords.z - SHADOW_BIAS > shadow_depth(cascade, coords.xy + offset) ? 0.0 : 1.0;
+            }
+        }
+        return lit / 9.0;
+    }
+    return 1.0;
+}
+
 void main() {
     vec2 uv = v_tex_coords;
     vec2 px = 1. / resolution;
@@ -20,15 +75,20 @@ void main() {
     vec4 albedo = texture(albedo, uv);
     vec4 position = texture(position, uv);
     vec4 normal = texture(normal, uv);
-    vec3 fog = vec3(0.4, 0.6, 1.0);
 
-    float vignette = 1.0 - 0.1 *length(uv - 0.5);
+    float occlusion = mix(1.0, texture(ssao, uv).r, ssao_strength);
+    float ambient = 0.8 * sky_light * occlusion;
+    float diffuse = max(dot(normal.xyz, normalize(light_dir)), 0.0);
+    if (diffuse > 0.0 && albedo.a > 0.0) {
+        highp vec3 world_position = (inverse_view_matrix * vec4(position.xyz, 1.0)).xyz;
+        diffuse *= sun_visibility(world_position, normal.xyz, length(position.xyz));
+    }
 
-    vec3 light_dir = normalize(vec3(sqrt(2.)));
-    float ambient = 0.8;
-    float diffuse = max(dot(normal.xyz, light_dir), 0.0);
+    vec3 out_color = albedo.rgb * min(vec3(ambient) + diffuse * light_color, vec3(2.0));
 
-    vec3 out_color = albedo.rgb * min(diffuse + ambient, 2.0);
+    // Positions are in view space, so the camera sits at the origin
+    float fog = smoothstep(fog_start, fog_end, length(position.xyz));
+    out_color = mix(out_color, fog_color, fog);
 
     color = vec4(out_color.rgb, albedo.a);
-}
\ No newline at end of file
+}
@@ -0,0 +1,15 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D input_texture;
+uniform float gamma;
+
+out vec4 color;
+
+void main() {
+    vec4 input_color = texture(input_texture, v_tex_coords);
+    color = vec4(pow(input_color.rgb, vec3(1.0 / gamma)), input_color.a);
+}
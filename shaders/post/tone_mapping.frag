@@ -0,0 +1,20 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D input_texture;
+uniform float exposure;
+
+out vec4 color;
+
+// Narkowicz's fit of the ACES filmic curve
+vec3 aces(vec3 x) {
+    return clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), 0.0, 1.0);
+}
+
+void main() {
+    vec4 input_color = texture(input_texture, v_tex_coords);
+    color = vec4(aces(input_color.rgb * exposure), input_color.a);
+}
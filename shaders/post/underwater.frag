@@ -0,0 +1,22 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D input_texture;
+uniform float time;
+
+out vec4 color;
+
+#define WATER_COLOR vec3(0.1, 0.3, 0.6)
+
+void main() {
+    // Wobble the image a little, like looking through moving water
+    vec2 uv = v_tex_coords;
+    uv.x += 0.004 * sin(uv.y * 30.0 + time * 2.0);
+    uv.y += 0.004 * cos(uv.x * 30.0 + time * 2.0);
+
+    vec4 input_color = texture(input_texture, uv);
+    color = vec4(mix(input_color.rgb, input_color.rgb * WATER_COLOR, 0.6), input_color.a);
+}
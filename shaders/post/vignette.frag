@@ -0,0 +1,15 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D input_texture;
+
+out vec4 color;
+
+void main() {
+    vec4 input_color = texture(input_texture, v_tex_coords);
+    float vignette = 1.0 - 0.3 * length(v_tex_coords - 0.5);
+    color = vec4(input_color.rgb * vignette, input_color.a);
+}
@@ -0,0 +1,12 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D texture_map;
+
+void main() {
+    // Gaps in leaves and plants let light through
+    if (texture(texture_map, v_tex_coords).a < 0.5) { discard; }
+}
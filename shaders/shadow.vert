@@ -0,0 +1,42 @@
+#version 310 es
+
+precision highp float;
+
+layout (location = 0) in vec3 position;
+layout (location = 1) in vec3 normal;
+layout (location = 2) in vec2 tex_coords;
+layout (location = 3) in float vtype;
+layout (location = 5) in float animation;
+
+// Must match MAX_ANIMATIONS in atlas.rs
+#define MAX_ANIMATIONS 32
+
+uniform mat4 model_matrix;
+// Sun's view and orthographic projection for the cascade being drawn
+uniform mat4 light_matrix;
+uniform float time;
+// Frame count, seconds per frame and distance in v between frames of each animated texture
+uniform vec3 animations[MAX_ANIMATIONS];
+
+out vec2 v_tex_coords;
+
+#define WIND_SPEED 2.0
+
+void main() {
+    // Sway with the wind exactly like cube.vert so shadows follow the plants casting them
+    vec4 position_worldspace = model_matrix * vec4(position, 1.0);
+    if(vtype == 1.0) {
+        position_worldspace.xz += mod(position.y, 1.0) * 0.03 * sin(WIND_SPEED * (time + position.y));
+    }
+    else if(vtype == 2.0) {
+        position_worldspace.xz += 0.03 * sin(WIND_SPEED * (time + position.y + 0.1415));
+    }
+
+    // Animated textures step down their strip of frames in the atlas
+    v_tex_coords = tex_coords;
+    if(animation > 0.0) {
+        vec3 frames = animations[int(animation) - 1];
+        v_tex_coords.y -= mod(floor(time / frames.y), frames.x) * frames.z;
+    }
+    gl_Position = light_matrix * position_worldspace;
+}
@@ -3,11 +3,39 @@
 precision mediump float;
 
 in vec2 v_tex_coords;
+in highp vec3 v_direction;
 
 uniform sampler2D skybox_texture;
 
+uniform vec3 sky_zenith_color;
+uniform vec3 sky_horizon_color;
+uniform highp vec3 sun_dir;
+uniform vec3 light_color;
+
 out vec4 color;
 
+#define SUN_SIZE 0.9995
+#define MOON_SIZE 0.9997
+
 void main() {
-    color = texture(skybox_texture, v_tex_coords);
-}
\ No newline at end of file
+    // The sun and moon discs are thinner than mediump can resolve near a dot product of 1
+    highp vec3 direction = normalize(v_direction);
+
+    float height = max(direction.y, 0.0);
+    vec3 sky = mix(sky_horizon_color, sky_zenith_color, sqrt(height));
+
+    // Take the clouds from the sky texture and light them like the terrain
+    vec4 texel = texture(skybox_texture, v_tex_coords);
+    float cloud = smoothstep(0.8, 0.95, texel.r) * step(0.0, direction.y);
+    sky = mix(sky, light_color * max(sky_zenith_color.b, 0.15) * 1.2, cloud);
+
+    if (dot(direction, sun_dir) > SUN_SIZE) {
+        sky = vec3(1.0, 0.95, 0.8);
+    }
+    highp vec3 moon_dir = vec3(-sun_dir.x, -sun_dir.y, sun_dir.z);
+    if (dot(direction, moon_dir) > MOON_SIZE) {
+        sky = vec3(0.85, 0.88, 0.95);
+    }
+
+    color = vec4(sky, 1.0);
+}
@@ -12,12 +12,14 @@ uniform mat4 view_matrix;
 uniform mat4 perspective_matrix;
 
 out vec2 v_tex_coords;
+out highp vec3 v_direction;
 
 void main() {
     vec4 position_worldspace = model_matrix * vec4(position, 1.0);
     vec4 position_viewspace = view_matrix * position_worldspace;
 
     v_tex_coords = tex_coords;
+    v_direction = position;
 
     gl_Position = perspective_matrix * position_viewspace;
-}
\ No newline at end of file
+}
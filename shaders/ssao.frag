@@ -10,30 +10,42 @@ layout (location = 2) uniform sampler2D albedo;
 layout (location = 3) uniform sampler2D ssao_noise;
 
 #define NUM_SAMPLES 64
+#define RADIUS 0.5
+#define BIAS 0.025
 uniform vec3 samples[NUM_SAMPLES];
+// Samples actually used, at most NUM_SAMPLES
+uniform float kernel_size;
 uniform float ssao_noise_size;
 
 uniform mat4 view_matrix;
 uniform mat4 perspective_matrix;
 
+// Size of the SSAO target, which may be smaller than the GBuffer
 uniform vec2 resolution;
 
+// How much ambient light reaches the fragment, 1.0 meaning nothing occludes it
 out float color;
 
 void main() {
 
     vec2 uv = v_tex_coords;
     vec2 noise_scale = resolution / ssao_noise_size;
-    
+
     // Sample textures from GBuffer
     vec3 f_position = texture(position, uv).xyz;
     vec3 f_normal = texture(normal, uv).xyz;
-    vec4 f_albedo =  texture(albedo, uv).rgba;
+    vec4 f_albedo = texture(albedo, uv).rgba;
     vec3 random_vec = texture(ssao_noise, uv * noise_scale).xyz;
 
+    // Nothing was drawn here, the skybox fills it in later
+    if (f_albedo.a == 0.0) {
+        color = 1.0;
+        return;
+    }
+
     // Transform normal vector to view space to calculate SSAO
     mat3 normal_matrix = transpose(inverse(mat3(view_matrix)));
-    f_normal = normal_matrix * f_normal;
+    f_normal = normalize(normal_matrix * f_normal);
 
     // Calculate SSAO
     vec3 tangent = normalize(random_vec - f_normal * dot(random_vec, f_normal));
@@ -41,21 +53,25 @@ void main() {
     mat3 TBN = mat3(tangent, bitangent, f_normal);
 
     float occlusion = 0.0;
-    for(int i=0;i<NUM_SAMPLES;++i) {
+    for (int i = 0; i < NUM_SAMPLES; ++i) {
+        if (float(i) >= kernel_size) { break; }
+
         vec3 sample_pos = TBN * samples[i];
-        sample_pos = f_position + sample_pos * 0.5;
+        sample_pos = f_position + sample_pos * RADIUS;
 
         vec4 offset = vec4(sample_pos, 1.0);
         offset = perspective_matrix * offset;
         offset.xyz /= offset.w;
         offset.xyz = offset.xyz * 0.5 + 0.5;
-        
+
+        // Empty sky behind the sample can't occlude anything
+        if (texture(albedo, offset.xy).a == 0.0) { continue; }
+
         float sample_depth = texture(position, offset.xy).z;
-        float sample_alpha = texture(albedo, offset.xy).a;
-        //float range_check = smoothstep(1.0, 0.0, 0.5 / abs(f_position.z - sample_depth));
-        occlusion += (sample_depth >= sample_pos.z + 0.025 ? 1.0 : 0.0 + (1.0 - sample_alpha));
+        float range_check = smoothstep(0.0, 1.0, RADIUS / abs(f_position.z - sample_depth));
+        // View space +z points away from the camera, so a surface nearer than the sample hides it
+        occlusion += (sample_depth <= sample_pos.z - BIAS ? 1.0 : 0.0) * range_check;
     }
-    occlusion /= float(NUM_SAMPLES);
-    
-    color = occlusion;
-}
\ No newline at end of file
+
+    color = 1.0 - occlusion / kernel_size;
+}
@@ -0,0 +1,24 @@
+#version 310 es
+
+precision mediump float;
+
+in vec2 v_tex_coords;
+
+uniform sampler2D ssao_input;
+
+uniform vec2 resolution;
+
+out float color;
+
+// Averages over the 4x4 tile the rotation noise repeats on, which removes its pattern
+void main() {
+    vec2 texel_size = 1.0 / resolution;
+    float result = 0.0;
+    for (int x = -2; x < 2; ++x) {
+        for (int y = -2; y < 2; ++y) {
+            vec2 offset = vec2(float(x), float(y)) * texel_size;
+            result += texture(ssao_input, v_tex_coords + offset).r;
+        }
+    }
+    color = result / 16.0;
+}
@@ -0,0 +1,39 @@
+#version 310 es
+
+precision mediump float;
+
+in vec3 v_position;
+in vec3 v_normal;
+in vec2 v_tex_coords;
+flat in float v_type;
+in float v_lighting;
+
+uniform float time;
+uniform float sky_light;
+uniform vec3 light_dir;
+uniform vec3 light_color;
+uniform vec3 fog_color;
+uniform float fog_start;
+uniform float fog_end;
+uniform sampler2D texture_map;
+
+out vec4 color;
+
+// Forward lit version of cube.frag followed by lighting.frag, for blocks blended over the scene
+void main() {
+    vec4 texel = texture(texture_map, v_tex_coords).rgba;
+    if (texel.a < 0.01) { discard; }
+
+    float lighting = min((v_lighting * sky_light / 16.0) + (1./16.), 1.0);
+    lighting = lighting * lighting * lighting;
+    vec3 albedo = 0.5 * lighting * texel.rgb + 0.5 * texel.rgb;
+
+    float ambient = 0.8 * sky_light;
+    float diffuse = max(dot(normalize(v_normal), normalize(light_dir)), 0.0);
+    vec3 out_color = albedo * min(vec3(ambient) + diffuse * light_color, vec3(2.0));
+
+    float fog = smoothstep(fog_start, fog_end, length(v_position));
+    out_color = mix(out_color, fog_color, fog);
+
+    color = vec4(out_color, texel.a * (1.0 - fog));
+}
@@ -0,0 +1,238 @@
+use voxel::engine::{EngineSettings, GameMode};
+
+pub const USAGE: &str = "Usage: voxel-game [OPTIONS]
+
+Options:
+  --worlds-dir <DIR>        Folder holding the worlds [default: .]
+  --list-worlds             List the worlds in the worlds folder and exit
+  --world <ID>              World to load and save, inside the worlds folder [default: from settings]
+  --new                     Create a new world, replacing any save in the world directory
+  --restore                 Load the saved world, failing if there isn't one
+  --seed <N>                Seed for a new world's terrain
+  --size <WIDTHxHEIGHT>     Window size in pixels [default: from settings]
+  --fullscreen              Open the window fullscreen on the primary monitor
+  --render-distance <N>     Chunks drawn around the player
+  --mode <MODE>             Game mode: survival, creative or spectator
+  --pos <X,Y,Z>             Place the player at this position
+  --settings <FILE>         Settings file [default: settings.json]
+  --headless                Run without a window, needs --ticks
+  --ticks <N>               Run N game ticks, save and exit, needs --headless
+  -h, --help                Print this help
+
+Without --new or --restore the saved world is loaded if there is one.";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorldChoice {
+    Auto,
+    New,
+    Restore,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub worlds_dir: String,
+    pub list_worlds: bool,
+    pub world: Option<String>,
+    pub world_choice: WorldChoice,
+    pub seed: Option<u32>,
+    pub size: Option<(i32, i32)>,
+    pub fullscreen: bool,
+    pub render_distance: Option<isize>,
+    pub game_mode: Option<GameMode>,
+    pub position: Option<(f32, f32, f32)>,
+    pub settings_path: Option<String>,
+    pub headless: bool,
+    pub ticks: Option<u64>,
+    pub help: bool,
+}
+
+impl Options {
+    /// Parse the command line arguments after the program name
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options {
+            worlds_dir: ".".to_string(),
+            list_worlds: false,
+            world: None,
+            world_choice: WorldChoice::Auto,
+            seed: None,
+            size: None,
+            fullscreen: false,
+            render_distance: None,
+            game_mode: None,
+            position: None,
+            settings_path: None,
+            headless: false,
+            ticks: None,
+            help: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("{} needs a value", arg))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--worlds-dir" => options.worlds_dir = value()?.clone(),
+                "--list-worlds" => options.list_worlds = true,
+                "--world" => options.world = Some(value()?.clone()),
+                "--new" | "--restore" => {
+                    if options.world_choice != WorldChoice::Auto {
+                        return Err("--new and --restore can't be used together".to_string());
+                    }
+                    options.world_choice = if arg == "--new" {
+                        WorldChoice::New
+                    } else {
+                        WorldChoice::Restore
+                    };
+                }
+                "--seed" => options.seed = Some(parse_number(arg, value()?)?),
+                "--size" => {
+                    let size = value()?;
+                    options.size = match size.split_once('x') {
+                        Some((width, height)) => Some((parse_number(arg, width)?, parse_number(arg, height)?)),
+                        None => return Err(format!("--size should look like 1920x900, not {}", size)),
+                    };
+                }
+                "--fullscreen" => options.fullscreen = true,
+                "--render-distance" => options.render_distance = Some(parse_number(arg, value()?)?),
+                "--mode" => {
+                    let mode = value()?;
+                    options.game_mode = Some(
+                        GameMode::from_name(mode).ok_or_else(|| format!("Unknown game mode {}", mode))?,
+                    );
+                }
+                "--pos" => {
+                    let position = value()?;
+                    let coords = position
+                        .split(',')
+                        .map(|coord| parse_number(arg, coord))
+                        .collect::<Result<Vec<f32>, String>>()?;
+                    options.position = match coords[..] {
+                        [x, y, z] => Some((x, y, z)),
+                        _ => return Err(format!("--pos should look like 0,64,0, not {}", position)),
+                    };
+                }
+                "--settings" => options.settings_path = Some(value()?.clone()),
+                "--headless" => options.headless = true,
+                "--ticks" => options.ticks = Some(parse_number(arg, value()?)?),
+                _ => return Err(format!("Unknown option {}", arg)),
+            }
+        }
+
+        if options.headless && options.ticks.is_none() {
+            return Err("--headless needs --ticks".to_string());
+        }
+        if options.ticks.is_some() && !options.headless {
+            return Err("--ticks only works with --headless".to_string());
+        }
+        if options.seed.is_some() && options.world_choice == WorldChoice::Restore {
+            return Err("--seed only applies to new worlds".to_string());
+        }
+        Ok(options)
+    }
+
+    /// Override `settings` with the options given for them, at startup and whenever the settings
+    /// file is reloaded
+    pub fn apply_to(&self, settings: &mut EngineSettings) {
+        if let Some(world) = &self.world {
+            settings.save_path = world.clone();
+        }
+        if let Some((width, height)) = self.size {
+            settings.window_width = width;
+            settings.window_height = height;
+        }
+        if let Some(render_distance) = self.render_distance {
+            settings.render_distance = render_distance;
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} got {}, which isn't a valid number", option, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn no_arguments_use_defaults() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options.worlds_dir, ".");
+        assert_eq!(options.world_choice, WorldChoice::Auto);
+        assert!(options.world.is_none() && options.seed.is_none() && !options.headless);
+    }
+
+    #[test]
+    fn flags_and_values_are_parsed() {
+        let options = parse(&[
+            "--worlds-dir", "saves", "--world", "flat", "--new", "--seed", "42", "--size", "1280x720",
+            "--fullscreen", "--render-distance", "8", "--mode", "creative", "--pos", "1.5, 70,-3",
+            "--settings", "custom.json", "--headless", "--ticks", "100",
+        ])
+        .unwrap();
+        assert_eq!(options.worlds_dir, "saves");
+        assert_eq!(options.world.as_deref(), Some("flat"));
+        assert_eq!(options.world_choice, WorldChoice::New);
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.size, Some((1280, 720)));
+        assert!(options.fullscreen);
+        assert_eq!(options.render_distance, Some(8));
+        assert_eq!(options.game_mode, Some(GameMode::Creative));
+        assert_eq!(options.position, Some((1.5, 70.0, -3.0)));
+        assert_eq!(options.settings_path.as_deref(), Some("custom.json"));
+        assert!(options.headless);
+        assert_eq!(options.ticks, Some(100));
+        assert!(parse(&["-h"]).unwrap().help);
+    }
+
+    #[test]
+    fn options_override_settings() {
+        let mut settings = EngineSettings::default();
+        parse(&["--world", "flat", "--size", "640x480", "--render-distance", "4"])
+            .unwrap()
+            .apply_to(&mut settings);
+        assert_eq!(settings.save_path, "flat");
+        assert_eq!((settings.window_width, settings.window_height), (640, 480));
+        assert_eq!(settings.render_distance, 4);
+
+        let default = EngineSettings::default();
+        let mut settings = EngineSettings::default();
+        parse(&[]).unwrap().apply_to(&mut settings);
+        assert_eq!(settings.save_path, default.save_path);
+        assert_eq!(settings.render_distance, default.render_distance);
+    }
+
+    #[test]
+    fn seed_without_new_only_fails_with_restore() {
+        assert_eq!(parse(&["--seed", "7"]).unwrap().seed, Some(7));
+        assert!(parse(&["--restore", "--seed", "7"]).is_err());
+        assert!(parse(&["--new", "--restore"]).is_err());
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        assert_eq!(parse(&["--fly"]).unwrap_err(), "Unknown option --fly");
+        assert!(parse(&["--mode", "adventure"]).is_err());
+    }
+
+    #[test]
+    fn missing_and_malformed_values_are_rejected() {
+        assert_eq!(parse(&["--seed"]).unwrap_err(), "--seed needs a value");
+        assert!(parse(&["--world"]).is_err());
+        assert!(parse(&["--seed", "abc"]).is_err());
+        assert!(parse(&["--size", "1280"]).is_err());
+        assert!(parse(&["--pos", "1,2"]).is_err());
+        assert!(parse(&["--headless"]).is_err());
+        assert!(parse(&["--ticks", "10"]).is_err());
+    }
+}
@@ -1,27 +1,111 @@
+mod cli;
+mod logger;
+
 use std::env;
+use std::path::Path;
+use std::time::Duration;
 
 use glfw::Context;
-use voxel::engine::{Engine, EngineEvent, PlayerInput};
+use voxel::engine::{Engine, EngineEvent, EngineSettings, PlayerInput, WorldList};
 use voxel::q_rsqrt;
 
-const WIDTH: i32 = 1920;
-const HEIGHT: i32 = 900;
-const GAME_SAVE_PATH: &str = "blockcraft";
+use cli::{Options, WorldChoice, USAGE};
+
+const SETTINGS_PATH: &str = "settings.json";
 
 fn main() {
+    logger::init();
 
     /* ********************************************** *
      * Collect arguments and initialize config values *
      * ********************************************** */
 
-    let args: Vec<String> = env::args().collect();
-    assert!(args.len() > 1);
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+    let worlds = WorldList::new(&options.worlds_dir);
+    if options.list_worlds {
+        print_worlds(&worlds);
+        return;
+    }
+
+    let settings_path = options.settings_path.as_deref().unwrap_or(SETTINGS_PATH);
+    let mut settings = if Path::new(settings_path).exists() {
+        EngineSettings::load(settings_path).unwrap_or_else(|e| {
+            println!("Using default settings, {}", e);
+            EngineSettings::default()
+        })
+    } else if options.settings_path.is_some() {
+        eprintln!("Settings file {} doesn't exist", settings_path);
+        std::process::exit(2);
+    } else {
+        EngineSettings::default()
+    };
+    options.apply_to(&mut settings);
+    if let Err(e) = settings.validate() {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+    let width = settings.window_width;
+    let height = settings.window_height;
+    let game_save_path = worlds.path(&settings.save_path).to_string_lossy().to_string();
+
+    let save_exists = Path::new(&game_save_path).join("savestate.json").exists();
+    let restore = match options.world_choice {
+        WorldChoice::Auto => save_exists,
+        WorldChoice::New => false,
+        WorldChoice::Restore => {
+            if !save_exists {
+                eprintln!("There is no saved world in {}", game_save_path);
+                std::process::exit(1);
+            }
+            true
+        }
+    };
+    if restore && options.seed.is_some() {
+        eprintln!("--seed only applies to new worlds, add --new to replace the save in {}", game_save_path);
+        std::process::exit(2);
+    }
 
-    let mut voxel_game = match args[1].as_str() {
-        "--restore" => Engine::load_from_save(GAME_SAVE_PATH),
-        "--new" => Engine::default(),
-        _ => Engine::default(),
+    let mut voxel_game = match (restore, options.seed, options.world_choice) {
+        (false, Some(seed), _) => Engine::new_world(settings, seed),
+        (false, None, WorldChoice::New) => Engine::new(settings),
+        // Restores the save, or grows a world made without one from the seed in its metadata
+        _ => Engine::open_world(&game_save_path, settings).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }),
     };
+    if let Some(game_mode) = options.game_mode {
+        voxel_game.set_game_mode(game_mode);
+    }
+    if let Some((x, y, z)) = options.position {
+        voxel_game.set_player_position(cgmath::Vector3::new(x, y, z));
+    }
+
+    /* ******************************************** *
+     * Headless: run the ticks asked for, then save *
+     * ******************************************** */
+
+    if let Some(ticks) = options.ticks {
+        voxel_game.init_engine();
+        voxel_game.resume();
+        while voxel_game.ticks() < ticks {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        voxel_game.pause();
+        voxel_game.save_to_file(&game_save_path);
+        return;
+    }
 
     /* ********************** *
      * Initialize game window *
@@ -34,12 +118,13 @@ fn main() {
     glfw.window_hint(glfw::WindowHint::OpenGlForwardCompat(true));
 
     let (mut window, events) = glfw
-        .create_window(
-            WIDTH as u32,
-            HEIGHT as u32,
-            "Voxel Game",
-            glfw::WindowMode::Windowed,
-        )
+        .with_primary_monitor(|glfw, monitor| {
+            let window_mode = match monitor {
+                Some(monitor) if options.fullscreen => glfw::WindowMode::FullScreen(monitor),
+                _ => glfw::WindowMode::Windowed,
+            };
+            glfw.create_window(width as u32, height as u32, "Voxel Game", window_mode)
+        })
         .expect("Failed to create GLFW window");
 
     gl::load_with(|s| window.get_proc_address(s) as *const _);
@@ -47,54 +132,32 @@ fn main() {
     window.set_key_polling(true);
     window.set_cursor_pos_polling(true);
     window.set_mouse_button_polling(true);
-    window.set_cursor_pos(WIDTH as f64 / 2.0, HEIGHT as f64 / 2.0);
+    window.set_cursor_pos(width as f64 / 2.0, height as f64 / 2.0);
     window.set_cursor_mode(glfw::CursorMode::Hidden);
 
-    println!("P - Pause\nO - Save");
+    println!("P - Pause\nO - Save\nF5 - Reload settings\nR - Respawn\nG - Cycle game mode\nF3 - Debug overlay\nShift - Sneak");
 
     /* ************************ *
      * Init + start game engine *
      * ************************ */
 
-    voxel_game.init_gl(WIDTH, HEIGHT);
+    voxel_game.init_gl(width, height);
     voxel_game.init_engine();
 
     /* ********************************************** *
      * Collect arguments and initialize config values *
      * ********************************************** */
 
-    const NUM_AVG_FRAMES: usize = 60;
-    let mut averages = [0f32; NUM_AVG_FRAMES];
-    let mut i = 0;
-
     let mut wasd_pressed = [false; 4];
     let mut jump = false;
+    let mut descend = false;
     let mut sprint = false;
-    let start_time = glfw.get_time() as f32;
-    let mut last_time = start_time;
 
     // Render + Input thread
     let mut should_close = false;
     while !should_close {
         should_close = window.should_close();
 
-        let current_time = glfw.get_time() as f32;
-        let delta_time = current_time - last_time;
-        averages[i] = 1.0 / delta_time;
-        i = if i < (NUM_AVG_FRAMES - 1) {
-            i + 1
-        } else {
-            let mut average_fps = 0.0;
-            for n in 0..NUM_AVG_FRAMES {
-                average_fps += averages[n];
-            }
-            average_fps /= NUM_AVG_FRAMES as f32;
-            window.should_close();
-            window.set_title(format!("Voxel Game - {} FPS", average_fps).as_str());
-            0
-        };
-        last_time = current_time;
-
         /* ******************************* *
          * Map GLFW events to player input *
          * ******************************* */
@@ -103,11 +166,11 @@ fn main() {
         for (_, event) in glfw::flush_messages(&events) {
             match event {
                 glfw::WindowEvent::CursorPos(x, y) => {
-                    let delta = (x - WIDTH as f64 / 2.0, y - HEIGHT as f64 / 2.0);
-                    window.set_cursor_pos(WIDTH as f64 / 2.0, HEIGHT as f64 / 2.0);
+                    let delta = (x - width as f64 / 2.0, y - height as f64 / 2.0);
+                    window.set_cursor_pos(width as f64 / 2.0, height as f64 / 2.0);
                     voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Look(
-                        0.001 * delta.1 as f32,
-                        0.001 * delta.0 as f32,
+                        delta.0 as f32,
+                        delta.1 as f32,
                     )));
                 }
                 glfw::WindowEvent::MouseButton(button, state, x) => {
@@ -115,6 +178,8 @@ fn main() {
                         glfw::MouseButton::Button1 => {
                             if state == glfw::Action::Press {
                                 voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Interact(false, true)));
+                            } else if state == glfw::Action::Release {
+                                voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::StopBreaking));
                             }
                         }
                         glfw::MouseButton::Button2 => {
@@ -123,8 +188,8 @@ fn main() {
                             }
                         }
                         glfw::MouseButton::Button3 => {
-                            if state == glfw::Action::Release {
-                                //voxel_game.should_interact = true;
+                            if state == glfw::Action::Press {
+                                voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::PickBlock));
                             }
                         }
                         _ => println!("{:?} {:?} {:?}", button, state, x),
@@ -156,6 +221,12 @@ fn main() {
                                 jump = false;
                             }
                         }
+                        glfw::Key::LeftShift => {
+                            descend = pressed_or_held;
+                            if state != glfw::Action::Repeat {
+                                voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Sneak(pressed_or_held)));
+                            }
+                        }
                         glfw::Key::LeftControl => {
                             if state == glfw::Action::Press {
                                 sprint = true;
@@ -167,7 +238,7 @@ fn main() {
                         glfw::Key::O => {
                             if state == glfw::Action::Release {
                                 voxel_game.pause();
-                                voxel_game.save_to_file(GAME_SAVE_PATH);
+                                voxel_game.save_to_file(&game_save_path);
                                 voxel_game.resume();
                             }
                         }
@@ -182,6 +253,39 @@ fn main() {
                             }
                         }
 
+                        glfw::Key::F5 => {
+                            if state == glfw::Action::Release {
+                                let reloaded = EngineSettings::load(settings_path).and_then(|mut settings| {
+                                    options.apply_to(&mut settings);
+                                    voxel_game.set_settings(settings)
+                                });
+                                match reloaded {
+                                    Ok(()) => println!("Reloaded {}", settings_path),
+                                    Err(e) => println!("Couldn't reload settings, {}", e),
+                                }
+                            }
+                        }
+
+                        glfw::Key::F3 => {
+                            if state == glfw::Action::Release {
+                                voxel_game.set_debug_overlay(!voxel_game.debug_overlay());
+                            }
+                        }
+
+                        glfw::Key::G => {
+                            if state == glfw::Action::Release {
+                                let game_mode = voxel_game.game_mode().next();
+                                voxel_game.set_game_mode(game_mode);
+                                println!("Game mode set to {}", game_mode.name());
+                            }
+                        }
+
+                        glfw::Key::R => {
+                            if state == glfw::Action::Release && voxel_game.is_dead() {
+                                voxel_game.respawn();
+                            }
+                        }
+
                         glfw::Key::Num1 => {
                             if state == glfw::Action::Press {
                                 voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Inventory(0)));
@@ -244,9 +348,13 @@ fn main() {
             y: 0.0,
             z: wasd_pressed[0] as i32 as f32 - wasd_pressed[2] as i32 as f32,
         };
-        move_direction *=
-            q_rsqrt(move_direction.x * move_direction.x + move_direction.z * move_direction.z);
-        if !wasd_pressed[0] && !wasd_pressed[1] && !wasd_pressed[2] && !wasd_pressed[3] {
+        if move_direction.x != 0.0 || move_direction.z != 0.0 {
+            move_direction *=
+                q_rsqrt(move_direction.x * move_direction.x + move_direction.z * move_direction.z);
+        }
+        // Only moves the player up and down while flying
+        move_direction.y = jump as i32 as f32 - descend as i32 as f32;
+        if !wasd_pressed[0] && !wasd_pressed[1] && !wasd_pressed[2] && !wasd_pressed[3] && !jump && !descend {
             voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Stop));
         } else {
             voxel_game.engine_event(EngineEvent::UserInput(PlayerInput::Walk(
@@ -269,3 +377,20 @@ fn main() {
         window.swap_buffers();
     }
 }
+
+fn print_worlds(worlds: &WorldList) {
+    let worlds = worlds.list();
+    if worlds.is_empty() {
+        println!("No worlds found");
+        return;
+    }
+    println!("{:<20} {:<24} {:>12} {:>12}", "ID", "NAME", "SEED", "PLAYED");
+    for world in worlds {
+        let play_time = format!("{}h{:02}m", world.play_time / 3600, world.play_time / 60 % 60);
+        let supported = if world.is_supported() { "" } else { " (needs a newer build)" };
+        println!(
+            "{:<20} {:<24} {:>12} {:>12}{}",
+            world.id, world.name, world.seed, play_time, supported
+        );
+    }
+}
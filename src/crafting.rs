@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use json::JsonValue;
+
+use crate::{
+    item::{item_id_by_name, ItemStack},
+    player::inventory::Inventory,
+};
+
+pub const GRID_SIZE: usize = 3;
+
+/// Item IDs laid out in the crafting grid, 0 for an empty slot
+pub type CraftingGrid = [[usize; GRID_SIZE]; GRID_SIZE];
+
+pub enum RecipeShape {
+    /// Rows of item IDs which must be laid out in this arrangement, or its mirror image
+    Shaped(Vec<Vec<usize>>),
+    /// Item IDs which may be laid out anywhere in the grid
+    Shapeless(Vec<usize>),
+}
+
+pub struct Recipe {
+    pub name: String,
+    pub shape: RecipeShape,
+    pub result: ItemStack,
+}
+
+impl Recipe {
+    /// Total count of each item ID consumed by the recipe
+    pub fn ingredients(&self) -> HashMap<usize, usize> {
+        let mut ingredients = HashMap::new();
+        let ids: Vec<usize> = match &self.shape {
+            RecipeShape::Shaped(rows) => rows.iter().flatten().copied().collect(),
+            RecipeShape::Shapeless(ids) => ids.clone(),
+        };
+        for id in ids.into_iter().filter(|id| *id != 0) {
+            *ingredients.entry(id).or_insert(0) += 1;
+        }
+        ingredients
+    }
+
+    /// Whether the items in `grid` make this recipe
+    pub fn matches(&self, grid: &CraftingGrid) -> bool {
+        match &self.shape {
+            RecipeShape::Shaped(rows) => {
+                let grid: Vec<Vec<usize>> = grid.iter().map(|row| row.to_vec()).collect();
+                let placed = trim_empty(&grid);
+                let mirrored: Vec<Vec<usize>> = rows
+                    .iter()
+                    .map(|row| row.iter().rev().copied().collect())
+                    .collect();
+                placed == *rows || placed == mirrored
+            }
+            RecipeShape::Shapeless(ids) => {
+                let mut placed: Vec<usize> =
+                    grid.iter().flatten().copied().filter(|id| *id != 0).collect();
+                let mut ids = ids.clone();
+                placed.sort_unstable();
+                ids.sort_unstable();
+                placed == ids
+            }
+        }
+    }
+
+    fn from_json(name: &str, recipe: &JsonValue) -> Result<Self, String> {
+        let shape = match recipe["type"].as_str() {
+            Some("shaped") => {
+                let mut key = HashMap::new();
+                for (symbol, item_name) in recipe["key"].entries() {
+                    let mut chars = symbol.chars();
+                    let symbol = match (chars.next(), chars.next()) {
+                        (Some(symbol), None) if symbol != ' ' => symbol,
+                        _ => {
+                            return Err(format!("Recipe \"{}\" has an invalid key \"{}\"", name, symbol))
+                        }
+                    };
+                    key.insert(symbol, item_id_by_recipe_name(name, item_name)?);
+                }
+
+                let mut rows = Vec::new();
+                for row in recipe["pattern"].members() {
+                    let row = row
+                        .as_str()
+                        .ok_or_else(|| format!("Recipe \"{}\" has a non-string pattern row", name))?;
+                    let mut ids = Vec::new();
+                    for symbol in row.chars() {
+                        ids.push(match symbol {
+                            ' ' => 0,
+                            _ => *key.get(&symbol).ok_or_else(|| {
+                                format!("Recipe \"{}\" uses \"{}\" without a key", name, symbol)
+                            })?,
+                        });
+                    }
+                    rows.push(ids);
+                }
+                if rows.len() > GRID_SIZE || rows.iter().any(|row| row.len() > GRID_SIZE) {
+                    return Err(format!("Recipe \"{}\" has a pattern which doesn't fit the grid", name));
+                }
+                let rows = trim_empty(&rows);
+                if rows.is_empty() {
+                    return Err(format!("Recipe \"{}\" has an empty pattern", name));
+                }
+                RecipeShape::Shaped(rows)
+            }
+            Some("shapeless") => {
+                let ids = recipe["ingredients"]
+                    .members()
+                    .map(|item_name| item_id_by_recipe_name(name, item_name))
+                    .collect::<Result<Vec<usize>, String>>()?;
+                if ids.is_empty() || ids.len() > GRID_SIZE * GRID_SIZE {
+                    return Err(format!("Recipe \"{}\" needs between 1 and 9 ingredients", name));
+                }
+                RecipeShape::Shapeless(ids)
+            }
+            _ => return Err(format!("Recipe \"{}\" has an unknown type", name)),
+        };
+
+        let result = &recipe["result"];
+        let result_id = item_id_by_recipe_name(name, &result["item"])?;
+        let result_count = result["count"].as_usize().unwrap_or(1);
+        if result_count == 0 {
+            return Err(format!("Recipe \"{}\" produces nothing", name));
+        }
+
+        Ok(Recipe {
+            name: name.to_string(),
+            shape,
+            result: ItemStack::new(result_id, result_count),
+        })
+    }
+}
+
+/// All known crafting recipes
+pub struct RecipeBook {
+    recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    /// Load recipes from JSON, checking that every item they mention exists
+    pub fn load(recipes_json: &str) -> Result<Self, String> {
+        let recipes = json::parse(recipes_json).map_err(|e| e.to_string())?;
+        let recipes = recipes
+            .entries()
+            .map(|(name, recipe)| Recipe::from_json(name, recipe))
+            .collect::<Result<Vec<Recipe>, String>>()?;
+        Ok(RecipeBook { recipes })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Recipe> {
+        self.recipes.iter().find(|recipe| recipe.name == name)
+    }
+
+    /// The recipe made by the items laid out in `grid`
+    pub fn find(&self, grid: &CraftingGrid) -> Option<&Recipe> {
+        self.recipes.iter().find(|recipe| recipe.matches(grid))
+    }
+
+    /// Recipes which `inventory` holds enough ingredients for
+    pub fn craftable<'a>(&'a self, inventory: &'a Inventory) -> impl Iterator<Item = &'a Recipe> {
+        self.recipes
+            .iter()
+            .filter(|recipe| inventory.has_ingredients(recipe))
+    }
+}
+
+fn item_id_by_recipe_name(recipe_name: &str, item_name: &JsonValue) -> Result<usize, String> {
+    item_name
+        .as_str()
+        .and_then(item_id_by_name)
+        .ok_or_else(|| format!("Recipe \"{}\" uses unknown item {}", recipe_name, item_name))
+}
+
+/// Cut the empty rows and columns from around the items in `rows`
+fn trim_empty(rows: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let used_rows: Vec<usize> = (0..rows.len())
+        .filter(|row| rows[*row].iter().any(|id| *id != 0))
+        .collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let used_columns: Vec<usize> = (0..width)
+        .filter(|column| rows.iter().any(|row| row.get(*column).is_some_and(|id| *id != 0)))
+        .collect();
+    match (used_rows.first(), used_rows.last(), used_columns.first(), used_columns.last()) {
+        (Some(top), Some(bottom), Some(left), Some(right)) => rows[*top..=*bottom]
+            .iter()
+            .map(|row| (*left..=*right).map(|column| row.get(column).copied().unwrap_or(0)).collect())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_RECIPES: &str = r#"{
+        "oak_plank" : {
+            "type" : "shapeless",
+            "ingredients" : ["Oak Log"],
+            "result" : { "item" : "Oak Plank", "count" : 4 }
+        },
+        "stone_stairs" : {
+            "type" : "shaped",
+            "pattern" : ["S  ", "SS ", "SSS"],
+            "key" : { "S" : "Stone" },
+            "result" : { "item" : "Stone Stairs", "count" : 4 }
+        }
+    }"#;
+
+    fn id(name: &str) -> usize {
+        item_id_by_name(name).unwrap()
+    }
+
+    #[test]
+    fn bundled_recipes_load() {
+        RecipeBook::load(include_str!("../assets/features/recipes.json")).unwrap();
+    }
+
+    #[test]
+    fn unknown_items_are_rejected() {
+        let recipes = r#"{ "bad" : {
+            "type" : "shapeless",
+            "ingredients" : ["Unobtainium"],
+            "result" : { "item" : "Stone" }
+        } }"#;
+        assert!(RecipeBook::load(recipes).is_err());
+    }
+
+    #[test]
+    fn matches_shaped_recipe_anywhere_in_grid() {
+        let book = RecipeBook::load(TEST_RECIPES).unwrap();
+        let stone = id("Stone");
+        let grid = [[stone, 0, 0], [stone, stone, 0], [stone, stone, stone]];
+        assert_eq!(book.find(&grid).unwrap().name, "stone_stairs");
+
+        let small_recipes = r#"{ "slab" : {
+            "type" : "shaped",
+            "pattern" : ["SS"],
+            "key" : { "S" : "Stone" },
+            "result" : { "item" : "Stone Slab" }
+        } }"#;
+        let book = RecipeBook::load(small_recipes).unwrap();
+        let grid = [[0, 0, 0], [0, stone, stone], [0, 0, 0]];
+        assert_eq!(book.find(&grid).unwrap().name, "slab");
+    }
+
+    #[test]
+    fn matches_mirrored_shape() {
+        let book = RecipeBook::load(TEST_RECIPES).unwrap();
+        let stone = id("Stone");
+        let grid = [[0, 0, stone], [0, stone, stone], [stone, stone, stone]];
+        assert_eq!(book.find(&grid).unwrap().name, "stone_stairs");
+
+        let upside_down = [[stone, stone, stone], [stone, stone, 0], [stone, 0, 0]];
+        assert!(book.find(&upside_down).is_none());
+    }
+
+    #[test]
+    fn matches_shapeless_recipe() {
+        let book = RecipeBook::load(TEST_RECIPES).unwrap();
+        let grid = [[0, 0, 0], [0, 0, 0], [0, id("Oak Log"), 0]];
+        assert_eq!(book.find(&grid).unwrap().name, "oak_plank");
+
+        let too_many = [[0, 0, 0], [0, id("Oak Log"), 0], [0, id("Oak Log"), 0]];
+        assert!(book.find(&too_many).is_none());
+    }
+
+    #[test]
+    fn crafting_consumes_inputs_and_produces_outputs() {
+        let book = RecipeBook::load(TEST_RECIPES).unwrap();
+        let mut inventory = Inventory::new();
+        inventory.insert(ItemStack::new(id("Stone"), 7));
+
+        assert!(inventory.craft(book.get("stone_stairs").unwrap()));
+        assert_eq!(inventory.count(id("Stone")), 1);
+        assert_eq!(inventory.count(id("Stone Stairs")), 4);
+    }
+
+    #[test]
+    fn insufficient_ingredients_leave_inventory_unchanged() {
+        let book = RecipeBook::load(TEST_RECIPES).unwrap();
+        let mut inventory = Inventory::new();
+        inventory.insert(ItemStack::new(id("Stone"), 5));
+
+        assert_eq!(book.craftable(&inventory).count(), 0);
+        assert!(!inventory.craft(book.get("stone_stairs").unwrap()));
+        assert_eq!(inventory.count(id("Stone")), 5);
+        assert_eq!(inventory.count(id("Stone Stairs")), 0);
+    }
+}
@@ -1,13 +1,31 @@
 use cgmath::Vector3;
 
+use crate::crafting::{CraftingGrid, RecipeBook};
+use crate::entity::falling_block::FallingBlock;
+use crate::item::{item_by_id, ItemStack, FIRST_NON_BLOCK_ITEM, ITEMS};
+use crate::graphics::hud::Hud;
+use crate::graphics::resources::GLRenderable;
 use crate::graphics::skybox::Skybox;
-use crate::physics::collision::{check_world_collision_axis, Collider};
+use crate::graphics::texture_pack::TexturePack;
+use crate::graphics::postprocess::PostProcessChain;
+pub use crate::graphics::postprocess::PostEffect;
+use crate::graphics::shadow::ShadowPass;
+use crate::graphics::ssao::SsaoPass;
+pub use crate::graphics::ssao::SsaoQuality;
+use crate::physics::collision::{check_world_collision_axis, has_support, rect_vs_rect, Collider};
 use crate::physics::physics_update::PhysicsUpdate;
-use crate::physics::vectormath::{self, Vec3Direction};
+use crate::physics::vectormath::{self, Vec3Direction, Y_VECTOR};
+pub use crate::player::camera::LookSettings;
+pub use crate::player::game_mode::GameMode;
+pub use crate::player::health::DamageSource;
+use crate::player::health::MAX_HEALTH;
+use crate::player::REACH_DISTANCE;
 pub use crate::player::PlayerInput;
+use crate::terrain::block::BLOCKS;
+use crate::terrain::block_state::BlockState;
 use crate::terrain::chunk::CHUNK_WIDTH;
-use crate::terrain::generation::TerrainGenConfig;
-use crate::terrain::{ChunkIndex, TerrainEvent};
+use crate::terrain::generation::{TerrainGenConfig, DEFAULT_SEED};
+use crate::terrain::{BlockUseResult, BlockWorldPos, ChunkIndex, TerrainEvent};
 use crate::{entity::EntityTrait, player::Player, terrain::Terrain};
 use crate::{graphics::resources::GLResources, physics::vectormath::Z_VECTOR};
 
@@ -19,9 +37,23 @@ use std::{
 
 use self::workers::EngineWorker;
 
+/// Where new worlds place the player, and where they respawn unless the save says otherwise
+const DEFAULT_SPAWN_POINT: Vector3<f32> = Vector3 {
+    x: 0.0,
+    y: 64.0,
+    z: 0.0,
+};
+
 mod graphics;
 mod save;
+mod settings;
 mod workers;
+mod world_time;
+mod worlds;
+
+pub use settings::EngineSettings;
+pub use world_time::WorldTime;
+pub use worlds::{WorldInfo, WorldList, WORLD_FORMAT_VERSION};
 
 #[derive(PartialEq, Eq, Debug)]
 enum PlayState {
@@ -38,6 +70,17 @@ struct EngineState {
     pub(crate) play_state: PlayState,
     pub(crate) elapsed_time: Duration,
     pub(crate) last_update: Instant,
+    pub(crate) open_interface: Option<BlockWorldPos>,
+    pub(crate) spawn_point: Vector3<f32>,
+    /// Game loop ticks run since the engine started
+    pub(crate) ticks: u64,
+    /// Value of `elapsed_time` when the world was last saved, so each save adds only the new play time
+    pub(crate) saved_elapsed_time: Duration,
+    pub(crate) world_time: WorldTime,
+    /// Whether the camera was inside a fluid on the last tick, which tints the screen
+    pub(crate) camera_in_fluid: bool,
+    /// How long the last tick took to run, not counting the sleep after it
+    pub(crate) tick_time: Duration,
 }
 
 impl Default for EngineState {
@@ -46,6 +89,13 @@ impl Default for EngineState {
             play_state: PlayState::Paused,
             elapsed_time: Duration::ZERO,
             last_update: Instant::now(),
+            open_interface: None,
+            spawn_point: DEFAULT_SPAWN_POINT,
+            ticks: 0,
+            saved_elapsed_time: Duration::ZERO,
+            world_time: WorldTime::default(),
+            camera_in_fluid: false,
+            tick_time: Duration::ZERO,
         }
     }
 }
@@ -53,58 +103,93 @@ impl Default for EngineState {
 pub struct Engine {
     player: Arc<RwLock<Box<Player>>>,
     terrain: Arc<RwLock<Terrain>>,
-    entities: Vec<Box<dyn EntityTrait>>,
+    entities: Arc<RwLock<Vec<Box<dyn EntityTrait>>>>,
     skybox: Arc<RwLock<Skybox>>,
+    recipes: Arc<RecipeBook>,
 
     event_queue: Arc<RwLock<Vec<EngineEvent>>>,
     engine_state: Arc<RwLock<EngineState>>,
+    settings: Arc<RwLock<EngineSettings>>,
+    /// Textures meshes are built with, replaced when the GL resources are created
+    texture_pack: Arc<RwLock<Arc<TexturePack>>>,
     
     width: AtomicI32,
     height: AtomicI32,
     render_distance: AtomicIsize,
     gl_resources: Arc<RwLock<GLResources>>,
+    /// Created with the other GL resources, none while SSAO is off
+    ssao_pass: Option<SsaoPass>,
+    /// Created with the other GL resources, none while shadows are off
+    shadow_pass: Option<ShadowPass>,
+    /// Created with the other GL resources
+    post_process: Option<PostProcessChain>,
+    hud: Hud,
 }
 
 impl Default for Engine {
     fn default() -> Self {
-        let player = Box::new(Player::new(Vector3::new(0.0, 64.0, 0.0), Z_VECTOR));
+        Self::new(EngineSettings::default())
+    }
+}
+
+impl Engine {
+    /// Create a new world, tuned by `settings`
+    pub fn new(settings: EngineSettings) -> Self {
+        Self::new_world(settings, DEFAULT_SEED)
+    }
+
+    /// Create a new world grown from `seed`, tuned by `settings`
+    pub fn new_world(settings: EngineSettings, seed: u32) -> Self {
+        let mut player = Box::new(Player::new(DEFAULT_SPAWN_POINT, Z_VECTOR));
+        player.apply_settings(&settings);
         
-        let mut terrain_config = TerrainGenConfig::default();
+        let mut terrain_config = TerrainGenConfig::with_seed(seed);
         terrain_config.load_features(include_str!("../assets/features/world_features.json"));
 
-        let terrain = Terrain::new(terrain_config);
+        let mut terrain = Terrain::new(terrain_config);
+        terrain.set_loading_radius(settings.loading_radius);
+
+        let recipes = RecipeBook::load(include_str!("../assets/features/recipes.json")).unwrap();
 
         Self {
             player: Arc::new(RwLock::new(player)),
             terrain: Arc::new(RwLock::new(terrain)),
-            entities: Vec::new(),
+            entities: Arc::new(RwLock::new(Vec::new())),
             skybox: Arc::new(RwLock::new(Skybox)),
+            recipes: Arc::new(recipes),
 
             
             event_queue: Arc::new(RwLock::new(Vec::new())),
             engine_state: Arc::new(RwLock::new(EngineState::default())),
-            
+            render_distance: settings.render_distance.into(),
+            settings: Arc::new(RwLock::new(settings)),
+            texture_pack: Arc::new(RwLock::new(Arc::new(TexturePack::builtin()))),
 
             width: 0.into(),
             height: 0.into(),
-            render_distance: 8.into(),
             gl_resources: Arc::new(RwLock::new(GLResources::new())),
+            ssao_pass: None,
+            shadow_pass: None,
+            post_process: None,
+            hud: Hud::new(),
         }
     }
-}
-
-impl Engine {
 
     pub fn init_engine(&mut self) {
-        self.terrain.start_thread(self.gl_resources.clone());
+        self.terrain
+            .start_thread(self.gl_resources.clone(), self.settings.clone(), self.texture_pack.clone());
         self.start_gameloop();
     }
 
     pub fn start_gameloop(&mut self) {
         let player = self.player.clone();
         let terrain = self.terrain.clone();
+        let entities = self.entities.clone();
         let engine_state = self.engine_state.clone();
         let event_queue = self.event_queue.clone();
+        let gl_resources = self.gl_resources.clone();
+        let settings = self.settings.clone();
+        let texture_pack = self.texture_pack.clone();
 
         std::thread::spawn(move || {
             loop {
@@ -116,16 +201,20 @@ impl Engine {
 
                     let mut player_rw = player.write().unwrap();
                     let mut terrain_rw = terrain.write().unwrap();
+                    let tick_start = Instant::now();
 
                     /****************************************
                      * Calculate delta_time since last tick *
                      ****************************************/
                     let delta_time = {
+                        let day_length = settings.read().unwrap().day_length;
                         let mut engine_state_rw = engine_state.write().unwrap();
                         let now = std::time::Instant::now();
                         let delta_time = now - engine_state_rw.last_update;
                         engine_state_rw.last_update = now;
                         engine_state_rw.elapsed_time += delta_time;
+                        engine_state_rw.ticks += 1;
+                        engine_state_rw.world_time.advance(delta_time.as_secs_f32(), day_length);
                         delta_time
                     };
 
@@ -136,79 +225,61 @@ impl Engine {
                     while let Some(event) = event_queue.write().unwrap().pop() {
                         match event {
                             EngineEvent::UserInput(player_input) => match player_input {
-                                PlayerInput::Interact(left_hand, right_hand) => {
-                                    let (camera_position, camera_forward) = player_rw.camera_pos_and_dir();
-                                    if right_hand {
-                                        if let Some((_world_pos, world_index)) = vectormath::dda(
+                                PlayerInput::PickBlock => {
+                                    if player_rw.game_mode() == GameMode::Creative {
+                                        if let Some((_world_pos, world_index, _hit_normal)) = vectormath::dda(
                                             &terrain_rw,
-                                            &camera_position,
-                                            &camera_forward,
-                                            6.0,
+                                            &player_rw.camera.position,
+                                            &player_rw.camera.forward,
+                                            REACH_DISTANCE,
                                         ) {
-                                            /*let dropped = terrain.set_block(0, &world_index);
-                                            if dropped != 0 {
-                                                let drop_world_pos = Vector3::new(
-                                                    world_index.x as f32 + 0.5,
-                                                    world_index.y as f32 + 0.5,
-                                                    world_index.z as f32 + 0.5,
-                                                );
-                                                let new_drop =
-                                                    Box::new(ItemDrop::new(dropped, drop_world_pos));
-                    
-                                                let verts = Box::new(block_drop_vertices(
-                                                    &BLOCKS[new_drop.block_id],
-                                                ));
-                                                let name = format!("item_{}", new_drop.block_id);
-                    
-                                                gl_resources.update_vao_buffer(name, verts);
-                                                self.entities.push(new_drop);
-                                            }*/
-                                            terrain_rw.event(TerrainEvent::ModifyBlock(world_index, 0));
+                                            let block_id = terrain_rw.block_at_world_pos(&world_index);
+                                            player_rw.inventory.set_selected_stack(ItemStack::new(block_id, 1));
                                         }
                                     }
+                                }
+                                PlayerInput::Interact(left_hand, right_hand)
+                                    if player_rw.game_mode().can_interact() && !player_rw.health.is_dead() =>
+                                {
+                                    if right_hand {
+                                        player_rw.mining.active = true;
+                                    }
                                     if left_hand {
-                                            if let Some((world_pos, world_index)) = vectormath::dda(
+                                            if let Some((_world_pos, world_index, hit_normal)) = vectormath::dda(
                                                 &terrain_rw,
                                                 &player_rw.camera.position,
                                                 &player_rw.camera.forward,
-                                                6.0,
+                                                REACH_DISTANCE,
                                             ) {
-                                                let mut diff = Vector3::new(
-                                                    world_pos.x - world_index.x as f32,
-                                                    world_pos.y - world_index.y as f32,
-                                                    world_pos.z - world_index.z as f32,
-                                                );
-                        
-                                                if diff.x == 0.0 {
-                                                    diff.x = -1.0;
-                                                } else if diff.x == 1.0 {
-                                                    diff.x = 1.0;
-                                                } else {
-                                                    diff.x = 0.0;
-                                                }
-                        
-                                                if diff.y == 0.0 {
-                                                    diff.y = -1.0;
-                                                } else if diff.y == 1.0 {
-                                                    diff.y = 1.0;
-                                                } else {
-                                                    diff.y = 0.0;
-                                                }
-                        
-                                                if diff.z == 0.0 {
-                                                    diff.z = -1.0;
-                                                } else if diff.z == 1.0 {
-                                                    diff.z = 1.0;
-                                                } else {
-                                                    diff.z = 0.0;
+                                                match terrain_rw.use_block(&world_index) {
+                                                    BlockUseResult::Pass => {
+                                                        let place_pos = world_index + hit_normal;
+                                                        if let Some(block_id) = player_rw.held_block() {
+                                                            let state = BlockState::for_placement(
+                                                                &BLOCKS[block_id],
+                                                                &hit_normal,
+                                                                &player_rw.camera.forward,
+                                                            );
+                                                            // Blocks can't go inside the player, where they'd trap and suffocate them
+                                                            let player_box = player_rw.bounding_box();
+                                                            let inside_player = BLOCKS[block_id]
+                                                                .collision_boxes(&state)
+                                                                .iter()
+                                                                .any(|model_box| rect_vs_rect(&model_box.world_rect(&place_pos), &player_box));
+                                                            // Check before taking the block, which would be lost if it couldn't go there
+                                                            if terrain_rw.can_place_at_world_pos(&place_pos)
+                                                                && !inside_player
+                                                                && player_rw.take_held_block().is_some()
+                                                            {
+                                                                terrain_rw.event(TerrainEvent::PlaceBlock(place_pos, block_id, state));
+                                                            }
+                                                        }
+                                                    }
+                                                    BlockUseResult::Consumed => {}
+                                                    BlockUseResult::OpenInterface(world_pos) => {
+                                                        engine_state.write().unwrap().open_interface = Some(world_pos);
+                                                    }
                                                 }
-                        
-                                                let offset = Vector3::new(
-                                                    diff.x as isize,
-                                                    diff.y as isize,
-                                                    diff.z as isize,
-                                                );
-                                                terrain_rw.event(TerrainEvent::ModifyBlock(world_index + offset, 1));
                                             }
                                         }
                                     }
@@ -218,6 +289,34 @@ impl Engine {
                         }
                     }
 
+                    /*****************************************
+                     * Advance breaking of the targeted block *
+                     *****************************************/
+
+                    if player_rw.mining.active && player_rw.game_mode().can_interact() && !player_rw.health.is_dead() {
+                        let (camera_position, camera_forward) = player_rw.camera_pos_and_dir();
+                        let target = vectormath::dda(&terrain_rw, &camera_position, &camera_forward, REACH_DISTANCE)
+                            .map(|(_world_pos, world_index, _hit_normal)| (world_index, terrain_rw.block_at_world_pos(&world_index)));
+                        let block = &BLOCKS[target.map(|(_, block_id)| block_id).unwrap_or(0)];
+                        let tool = player_rw.held_tool();
+                        let broken = if player_rw.game_mode() == GameMode::Creative {
+                            // Creative breaks instantly, one block per click
+                            player_rw.mining.active = false;
+                            target
+                        } else {
+                            player_rw.mining.update(target, block, tool, delta_time.as_secs_f32())
+                        };
+                        if let Some((world_index, block_id)) = broken {
+                            terrain_rw.event(TerrainEvent::ModifyBlock(world_index, 0));
+                            if player_rw.game_mode() == GameMode::Survival {
+                                player_rw.add_to_inventory(ItemStack::new(block_id, 1));
+                                if tool.is_some() {
+                                    player_rw.inventory.damage_selected();
+                                }
+                            }
+                        }
+                    }
+
                     /*******************************************
                      * Tick all objects in the array in order: *
                      * 1. Terrain                              *
@@ -244,54 +343,97 @@ impl Engine {
 
                     let movement_delta = player_rw.movement_delta();
 
-                    player_rw.position.x += movement_delta.x;
-                    let overlap_x =
-                        check_world_collision_axis(Vec3Direction::X, player_rw.bounding_box(), &terrain_rw);
-                    player_rw.correct_position_axis(Vec3Direction::X, overlap_x);
+                    if player_rw.game_mode().has_collision() {
+                        // Sneaking stops the player from walking off the edge of whatever they stand on
+                        let edge_protection = player_rw.is_sneaking()
+                            && player_rw.grounded
+                            && has_support(&player_rw.bounding_box(), &terrain_rw);
 
-                    player_rw.position.y += movement_delta.y;
-                    let overlap_y =
-                        check_world_collision_axis(Vec3Direction::Y, player_rw.bounding_box(), &terrain_rw);
-                    player_rw.correct_position_axis(Vec3Direction::Y, overlap_y);
+                        let previous_x = player_rw.position.x;
+                        player_rw.position.x += movement_delta.x;
+                        let overlap_x =
+                            check_world_collision_axis(Vec3Direction::X, player_rw.bounding_box(), &terrain_rw);
+                        player_rw.correct_position_axis(Vec3Direction::X, overlap_x);
+                        if edge_protection && !has_support(&player_rw.bounding_box(), &terrain_rw) {
+                            player_rw.position.x = previous_x;
+                        }
 
-                    player_rw.position.z += movement_delta.z;
-                    let overlap_z =
-                        check_world_collision_axis(Vec3Direction::Z, player_rw.bounding_box(), &terrain_rw);
-                    player_rw.correct_position_axis(Vec3Direction::Z, overlap_z);
+                        player_rw.position.y += movement_delta.y;
+                        let overlap_y =
+                            check_world_collision_axis(Vec3Direction::Y, player_rw.bounding_box(), &terrain_rw);
+                        player_rw.correct_position_axis(Vec3Direction::Y, overlap_y);
+
+                        let previous_z = player_rw.position.z;
+                        player_rw.position.z += movement_delta.z;
+                        let overlap_z =
+                            check_world_collision_axis(Vec3Direction::Z, player_rw.bounding_box(), &terrain_rw);
+                        player_rw.correct_position_axis(Vec3Direction::Z, overlap_z);
+                        if edge_protection && !has_support(&player_rw.bounding_box(), &terrain_rw) {
+                            player_rw.position.z = previous_z;
+                        }
+                    } else {
+                        player_rw.translate_relative(movement_delta);
+                    }
+
+                    let head_position = player_rw.position + player_rw.height() * Y_VECTOR;
+                    let head_index = BlockWorldPos {
+                        x: head_position.x.floor() as isize,
+                        y: head_position.y.floor() as isize,
+                        z: head_position.z.floor() as isize,
+                    };
+                    let head_block = &BLOCKS[terrain_rw.block_at_world_pos(&head_index)];
+                    let head_in_solid = head_block.solid && !head_block.transparent;
+                    // There are no fluid blocks to drown in yet
+                    let head_in_fluid = false;
+                    if player_rw.game_mode().takes_damage() {
+                        player_rw.health.update(head_in_solid, head_in_fluid, delta_time.as_secs_f32());
+                    }
+                    engine_state.write().unwrap().camera_in_fluid = head_in_fluid;
 
 
                     /*******************
                      * Update entities *
                      *******************/
                     
-                    //TODO: Uncomment and re-enable entities
-                    /*
-                    for entity in &mut self.entities {
-                        let terrain = self.terrain.read().unwrap();
+                    let mut entities_rw = entities.write().unwrap();
+
+                    let gravity = settings.read().unwrap().gravity;
+                    for (world_pos, block_id) in terrain_rw.take_falling_blocks() {
+                        let falling_block = Box::new(FallingBlock::new(block_id, world_pos, gravity));
+                        let texture_pack = texture_pack.read().unwrap().clone();
+                        falling_block.init_gl_resources(&mut gl_resources.write().unwrap(), &texture_pack);
+                        entities_rw.push(falling_block);
+                    }
+
+                    for entity in entities_rw.iter_mut() {
                         entity.update_physics(delta_time.as_secs_f32());
 
                         let movement_delta = entity.movement_delta();
 
                         entity.translate_relative(Vector3::new(movement_delta.x, 0.0, 0.0));
                         let overlap_x =
-                            check_world_collision_axis(Vec3Direction::X, entity.bounding_box(), &terrain);
+                            check_world_collision_axis(Vec3Direction::X, entity.bounding_box(), &terrain_rw);
                         entity.correct_position_axis(Vec3Direction::X, overlap_x);
 
                         entity.translate_relative(Vector3::new(0.0, movement_delta.y, 0.0));
                         let overlap_y =
-                            check_world_collision_axis(Vec3Direction::Y, entity.bounding_box(), &terrain);
+                            check_world_collision_axis(Vec3Direction::Y, entity.bounding_box(), &terrain_rw);
                         entity.correct_position_axis(Vec3Direction::Y, overlap_y);
 
                         entity.translate_relative(Vector3::new(0.0, 0.0, movement_delta.z));
                         let overlap_z =
-                            check_world_collision_axis(Vec3Direction::Z, entity.bounding_box(), &terrain);
+                            check_world_collision_axis(Vec3Direction::Z, entity.bounding_box(), &terrain_rw);
                         entity.correct_position_axis(Vec3Direction::Z, overlap_z);
                     }
-                    */
+
+                    entities_rw.retain_mut(|entity| entity.tick(&mut terrain_rw));
+
+                    engine_state.write().unwrap().tick_time = tick_start.elapsed();
                 }
 
                 //TODO: Replace the below statement with logic to target ~20tps
-                std::thread::sleep(Duration::from_millis(5));
+                let tick_sleep_ms = settings.read().unwrap().tick_sleep_ms;
+                std::thread::sleep(Duration::from_millis(tick_sleep_ms));
             }
         });
     }
@@ -314,10 +456,184 @@ impl Engine {
         }
     }
 
+    /// Number of game loop ticks run so far
+    pub fn ticks(&self) -> u64 {
+        self.engine_state.read().unwrap().ticks
+    }
+
+    /// Seed the world's terrain is generated from
+    pub fn seed(&self) -> u32 {
+        self.terrain.read().unwrap().terrain_config().seed()
+    }
+
+    pub fn player_position(&self) -> Vector3<f32> {
+        self.player.read().unwrap().position
+    }
+
+    /// Move the player straight to `position`
+    pub fn set_player_position(&mut self, position: Vector3<f32>) {
+        self.player.write().unwrap().teleport(position);
+    }
+
+    /// Where the world is in its day/night cycle, see [`WorldTime`]
+    pub fn world_time(&self) -> WorldTime {
+        self.engine_state.read().unwrap().world_time
+    }
+
+    pub fn time_of_day(&self) -> f32 {
+        self.world_time().time_of_day()
+    }
+
+    /// Jump to `time_of_day` within the current day, 0 being midnight and 0.5 noon
+    pub fn set_time_of_day(&mut self, time_of_day: f32) {
+        self.engine_state.write().unwrap().world_time.set_time_of_day(time_of_day);
+    }
+
+    pub fn settings(&self) -> EngineSettings {
+        self.settings.read().unwrap().clone()
+    }
+
+    /// Switch to `settings` while the game runs. The window size and save path are only read at
+    /// startup, so changes to them wait for the next launch.
+    pub fn set_settings(&mut self, settings: EngineSettings) -> Result<(), String> {
+        settings.validate()?;
+        self.player.write().unwrap().apply_settings(&settings);
+        self.terrain.write().unwrap().set_loading_radius(settings.loading_radius);
+        self.render_distance
+            .store(settings.render_distance, std::sync::atomic::Ordering::Relaxed);
+        *self.settings.write().unwrap() = settings;
+        Ok(())
+    }
+
+    /// Read the settings file at `path` again and apply it
+    pub fn reload_settings(&mut self, path: &str) -> Result<(), String> {
+        let settings = EngineSettings::load(path)?;
+        self.set_settings(settings)
+    }
+
     pub fn is_paused(&self) -> bool {
         self.engine_state.read().unwrap().play_state == PlayState::Paused
     }
 
+    /// Position of the block whose interface the player has opened, if any
+    pub fn open_interface(&self) -> Option<BlockWorldPos> {
+        self.engine_state.read().unwrap().open_interface
+    }
+
+    pub fn close_interface(&mut self) {
+        self.engine_state.write().unwrap().open_interface = None;
+    }
+
+    /// Craft the recipe called `recipe_name` from the player's inventory
+    pub fn craft(&mut self, recipe_name: &str) -> bool {
+        match self.recipes.get(recipe_name) {
+            Some(recipe) => self.player.write().unwrap().craft(recipe),
+            None => false,
+        }
+    }
+
+    /// Craft whatever the blocks laid out in `grid` make, taking the ingredients from the player's inventory
+    pub fn craft_grid(&mut self, grid: &CraftingGrid) -> bool {
+        match self.recipes.find(grid) {
+            Some(recipe) => self.player.write().unwrap().craft(recipe),
+            None => false,
+        }
+    }
+
+    /// Names of the recipes the player has enough ingredients to craft
+    pub fn craftable_recipes(&self) -> Vec<String> {
+        let player = self.player.read().unwrap();
+        self.recipes
+            .craftable(&player.inventory)
+            .map(|recipe| recipe.name.clone())
+            .collect()
+    }
+
+    pub fn game_mode(&self) -> GameMode {
+        self.player.read().unwrap().game_mode()
+    }
+
+    pub fn set_game_mode(&mut self, game_mode: GameMode) {
+        self.player.write().unwrap().set_game_mode(game_mode);
+    }
+
+    /// Every item a creative mode player can pick from
+    pub fn creative_palette(&self) -> Vec<usize> {
+        (0..FIRST_NON_BLOCK_ITEM + ITEMS.len())
+            .filter(|item_id| item_by_id(*item_id).is_some())
+            .collect()
+    }
+
+    /// Put `item_id` from the creative palette in the player's hand
+    pub fn pick_palette_item(&mut self, item_id: usize) -> bool {
+        let mut player = self.player.write().unwrap();
+        if player.game_mode() != GameMode::Creative || item_by_id(item_id).is_none() {
+            return false;
+        }
+        player.inventory.set_selected_stack(ItemStack::new(item_id, 1));
+        true
+    }
+
+    pub fn look_settings(&self) -> LookSettings {
+        self.player.read().unwrap().look_settings
+    }
+
+    pub fn set_look_settings(&mut self, look_settings: LookSettings) {
+        self.player.write().unwrap().look_settings = look_settings;
+    }
+
+    pub fn health(&self) -> f32 {
+        self.player.read().unwrap().health.points()
+    }
+
+    pub fn max_health(&self) -> f32 {
+        MAX_HEALTH
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.player.read().unwrap().health.is_dead()
+    }
+
+    /// What last hurt the player, which is what killed them if they are dead
+    pub fn last_damage(&self) -> Option<DamageSource> {
+        self.player.read().unwrap().health.last_damage()
+    }
+
+    pub fn spawn_point(&self) -> Vector3<f32> {
+        self.engine_state.read().unwrap().spawn_point
+    }
+
+    pub fn set_spawn_point(&mut self, spawn_point: Vector3<f32>) {
+        self.engine_state.write().unwrap().spawn_point = spawn_point;
+    }
+
+    /// Bring the player back to life at the world's spawn point
+    pub fn respawn(&mut self) {
+        let spawn_point = self.spawn_point();
+        self.player.write().unwrap().respawn(spawn_point);
+    }
+
+    /// Whether the position, performance and chunk loading stats are drawn over the game
+    pub fn debug_overlay(&self) -> bool {
+        self.hud.debug_overlay
+    }
+
+    pub fn set_debug_overlay(&mut self, shown: bool) {
+        self.hud.debug_overlay = shown;
+    }
+
+    /// The block the crosshair is on and the normal of its face the player is looking at
+    pub fn targeted_block(&self) -> Option<(BlockWorldPos, BlockWorldPos)> {
+        let player = self.player.read().unwrap();
+        let terrain = self.terrain.read().unwrap();
+        player.targeted_block(&terrain)
+    }
+
+    /// The block the player is breaking and the crack overlay stage to draw on it
+    pub fn block_breaking_stage(&self) -> Option<(BlockWorldPos, usize)> {
+        self.player.read().unwrap().mining.crack_stage()
+    }
+
     pub fn engine_event(&mut self, event: EngineEvent) {
         self.event_queue.write().unwrap().push(event);
     }
@@ -1,16 +1,24 @@
-use cgmath::{Matrix4, Vector2};
+use std::{path::Path, sync::Arc};
+
+use cgmath::{Matrix4, SquareMatrix, Vector2};
 
 use crate::c_str;
+use crate::terrain::{chunk::CHUNK_WIDTH, ChunkIndex};
 use crate::{engine::Engine, player::camera::perspective_matrix};
 
 use crate::graphics::{
     depthbuffer::Depthbuffer,
+    ssao::SsaoPass,
     framebuffer::Framebuffer,
+    hud::DebugInfo,
     mesh::FULLSCREEN_QUAD,
     resources::GLRenderable,
+    postprocess::PostProcessChain,
     shader::Shader,
+    shadow::ShadowPass,
     source::{COMPOSITE_FRAG_SRC, LIGHTING_FRAG_SRC, POSTPROCESS_FRAG_SRC, SCREENQUAD_VERT_SRC},
     texture::{Texture, TextureFormat},
+    texture_pack::TexturePack,
     uniform::Uniform,
 };
 
@@ -47,33 +55,42 @@ impl Engine {
             gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut framebuffer_id);
         }
 
-        /*let ssao_program = graphics::shader::Shader::new(SCREENQUAD_VERT_SRC, SSAO_FRAG_SRC).unwrap();
-
-        ssao_program.use_program();
-        let mut ssao_kernel = [Vector3::zero(); 64];
-        for (i, s) in &mut ssao_kernel.iter_mut().enumerate() {
-            let sample: Vector3<f32> = Vector3::new(
-                rand::random::<f32>() * 2.0 - 1.0,
-                rand::random::<f32>() * 2.0 - 1.0,
-                rand::random::<f32>(),
-            ).normalize() * rand::random();
-            let sample_name_rust = format!("samples[{}]", i);
-            let sample_name = std::ffi::CString::new(sample_name_rust.as_str()).unwrap();
-            ssao_program.set_vec3(&sample_name, &sample);
-            *s = sample;
-        }
+        // Ambient occlusion, at half resolution on low quality
+        let ssao_quality = self.settings.read().unwrap().ssao;
+        self.ssao_pass = SsaoPass::new(ssao_quality, width, height);
 
-        const SSAO_NOISE_SIZE: usize = 4;
-        ssao_program.set_float(unsafe {c_str!("ssao_noise_size")}, SSAO_NOISE_SIZE as f32);
-        let mut ssao_noise = [Vector3::zero(); SSAO_NOISE_SIZE*SSAO_NOISE_SIZE];
-        for pixel in ssao_noise.iter_mut() {
-            *pixel = Vector3::new(
-                rand::random::<f32>() * 2.0 - 1.0,
-                rand::random::<f32>() * 2.0 - 1.0,
-                0.0
-            );
+        // Shadow maps from the sun
+        self.shadow_pass = {
+            let settings = self.settings.read().unwrap();
+            settings
+                .shadows
+                .then(|| ShadowPass::new(settings.shadow_resolution, settings.shadow_cascades))
+        };
+
+        // Block textures, drawn with the built-in ones if the configured pack can't be loaded
+        let texture_pack = match &self.settings.read().unwrap().texture_pack {
+            Some(directory) => match TexturePack::load(Path::new(directory)) {
+                Ok(pack) => {
+                    info!("Using texture pack {}", pack.name);
+                    pack
+                }
+                Err(e) => {
+                    error!("Couldn't load texture pack: {}", e);
+                    TexturePack::builtin()
+                }
+            },
+            None => TexturePack::builtin(),
+        };
+        let texture_pack = Arc::new(texture_pack);
+        {
+            // Swapped while holding the terrain lock, so no mesh built with the old pack outlives the
+            // rebuild
+            let terrain = self.terrain.read().unwrap();
+            *self.texture_pack.write().unwrap() = texture_pack.clone();
+            terrain.rebuild_all_meshes();
         }
-        let ssao_noise_texture = Texture::from_vector3_array(&ssao_noise, SSAO_NOISE_SIZE as i32, SSAO_NOISE_SIZE as i32);*/
+        let texture_quality = self.settings.read().unwrap().texture_quality;
+        let terrain_texture = Texture::from_mip_levels(texture_pack.atlas.levels(), texture_quality);
 
         // Deferred rendering pass
         let gbuffer_position = Texture::empty(width, height, TextureFormat::Float);
@@ -101,8 +118,9 @@ impl Engine {
         let composite_framebuffer =
             Framebuffer::with_textures(&[("color", composite_color)], Some(composite_depthbuffer));
 
-        // Postprocessing shader
+        // Postprocessing shader, copying the composite output to the screen when no effect runs
         let postprocess_program = Shader::new(SCREENQUAD_VERT_SRC, POSTPROCESS_FRAG_SRC).unwrap();
+        let post_process = PostProcessChain::new(&self.settings.read().unwrap().post_effects);
 
         {
             let mut gl_resources = self.gl_resources.write().unwrap();
@@ -116,6 +134,8 @@ impl Engine {
             gl_resources.add_shader("composite", composite_program);
             gl_resources.add_shader("postprocess", postprocess_program);
 
+            gl_resources.add_texture("terrain", terrain_texture);
+
             // Graphics resources for world components
 
             gl_resources.add_vao(
@@ -123,18 +143,29 @@ impl Engine {
                 Box::new(Vec::from(FULLSCREEN_QUAD)),
             );
 
-            self.skybox.write().unwrap().init_gl_resources(&mut gl_resources);
+            if let Some(ssao_pass) = &self.ssao_pass {
+                ssao_pass.init_gl_resources(&mut gl_resources);
+            }
+            if let Some(shadow_pass) = &self.shadow_pass {
+                shadow_pass.init_gl_resources(&mut gl_resources);
+            }
+            post_process.init_gl_resources(&mut gl_resources, width, height);
+            self.hud.init_gl_resources(&mut gl_resources);
+
+            self.skybox.write().unwrap().init_gl_resources(&mut gl_resources, &texture_pack);
 
             self.terrain
                 .write()
                 .unwrap()
-                .init_gl_resources(&mut gl_resources);
+                .init_gl_resources(&mut gl_resources, &texture_pack);
 
-            for entity in self.entities.iter() {
-                entity.init_gl_resources(&mut gl_resources);
+            for entity in self.entities.read().unwrap().iter() {
+                entity.init_gl_resources(&mut gl_resources, &texture_pack);
             }
         }
 
+        self.post_process = Some(post_process);
+
         self.resume();
     }
 
@@ -145,23 +176,73 @@ impl Engine {
     pub fn draw(&mut self) {
         let player = self.player.read().unwrap();
         let terrain = self.terrain.read().unwrap();
+        let texture_pack = self.texture_pack.read().unwrap().clone();
+        let width = self.width.load(std::sync::atomic::Ordering::Relaxed);
+        let height = self.height.load(std::sync::atomic::Ordering::Relaxed);
         {
-            self.gl_resources
-                .write()
-                .unwrap()
-                .process_vao_buffer_updates(2);
+            let mut gl_resources = self.gl_resources.write().unwrap();
+            let debug_info = self.hud.debug_overlay.then(|| {
+                let chunk_index = ChunkIndex::new(
+                    (player.position.x / CHUNK_WIDTH as f32).floor() as isize,
+                    (player.position.z / CHUNK_WIDTH as f32).floor() as isize,
+                );
+                DebugInfo {
+                    position: player.position,
+                    chunk_index,
+                    biome: terrain
+                        .terrain_config()
+                        .get_biome([player.position.x as f64, player.position.z as f64])
+                        .name(),
+                    tick_time: self.engine_state.read().unwrap().tick_time,
+                    visible_chunks: terrain.visible_chunk_count(),
+                    loaded_chunks: terrain.loaded_chunk_count(),
+                    pending_vao_updates: gl_resources.pending_vao_updates(),
+                }
+            });
+
+            let vao_uploads_per_frame = self.settings.read().unwrap().vao_uploads_per_frame;
+            gl_resources.process_vao_buffer_updates(vao_uploads_per_frame);
+            self.hud
+                .update(&mut gl_resources, &player.inventory, &texture_pack, debug_info, width, height);
         }
 
         let gl_resources = self.gl_resources.read().unwrap();
-        let width = self.width.load(std::sync::atomic::Ordering::Relaxed);
-        let height = self.height.load(std::sync::atomic::Ordering::Relaxed);
         let render_distance = self.render_distance.load(std::sync::atomic::Ordering::Relaxed);
-        let elapsed_time = {
-            self.engine_state.read().unwrap().elapsed_time
+        let (elapsed_time, world_time, camera_in_fluid) = {
+            let engine_state = self.engine_state.read().unwrap();
+            (engine_state.elapsed_time, engine_state.world_time, engine_state.camera_in_fluid)
+        };
+        let (loading_radius, exposure, gamma) = {
+            let settings = self.settings.read().unwrap();
+            (settings.loading_radius, settings.exposure, settings.gamma)
         };
+        // Fog closes in a chunk before the edge of the loaded terrain so chunks fade in instead of popping
+        let fog_end = ((render_distance.min(loading_radius) - 1).max(1) * CHUNK_WIDTH as isize) as f32;
+        let (sky_zenith_color, sky_horizon_color) = world_time.sky_colors();
+        let animations = texture_pack.atlas.animations();
 
         let screenquad = gl_resources.get_vao("screenquad").unwrap();
 
+        /* *************************************** *
+         * Render shadow casters from the sun side *
+         * *************************************** */
+
+        let light_matrices = match &self.shadow_pass {
+            Some(shadow_pass) => {
+                let light_matrices =
+                    shadow_pass.light_matrices(player.camera.position, world_time.light_direction());
+                shadow_pass.draw(
+                    &gl_resources,
+                    &[&*terrain],
+                    &light_matrices,
+                    elapsed_time.as_secs_f32(),
+                    &animations,
+                );
+                light_matrices
+            }
+            None => Vec::new(),
+        };
+
         /* ************************************ *
          * Render terrain + entities to GBuffer *
          * ************************************ */
@@ -182,16 +263,29 @@ impl Engine {
             ("perspective_matrix", Box::new(perspective_matrix)),
             ("view_matrix", Box::new(view_matrix)),
             ("time", Box::new(elapsed_time.as_secs_f32())),
+            ("animations", Box::new(animations.clone())),
+            ("sky_light", Box::new(world_time.sky_light())),
         ];
 
         terrain.draw(&gl_resources, &geometry_uniforms);
 
-        for entity in &self.entities {
+        for entity in self.entities.read().unwrap().iter() {
             entity.draw(&gl_resources, &geometry_uniforms);
         }
 
         gbuffer_fbo.unbind();
 
+        /* ************************************* *
+         * Ambient occlusion from GBuffer depths *
+         * ************************************* */
+
+        if let Some(ssao_pass) = &self.ssao_pass {
+            ssao_pass.draw(&gl_resources, screenquad, view_matrix, perspective_matrix);
+            unsafe {
+                gl::Viewport(0, 0, width, height);
+            }
+        }
+
         /* ********************************************* *
          * Calculate lighting for GBuffer-rendered items *
          * ********************************************* */
@@ -208,12 +302,33 @@ impl Engine {
         ]);
 
         lighting_program.use_program();
+        // The SSAO target is sampled by UV, so a half resolution one stretches over the whole screen
+        let ssao_strength = match gl_resources.get_framebuffer("ssao_blur") {
+            Some(ssao_blur_fbo) if self.ssao_pass.is_some() => {
+                ssao_blur_fbo.bind_render_textures_to_current_fb(&[("color", 0)]);
+                1.0
+            }
+            _ => 0.0,
+        };
+        lighting_program.set_texture(unsafe { c_str!("ssao") }, 0);
+        ssao_strength.set_as_uniform(lighting_program, "ssao_strength");
+        match &self.shadow_pass {
+            Some(shadow_pass) => shadow_pass.bind_for_lighting(&gl_resources, lighting_program, 4, &light_matrices),
+            None => 0.0.set_as_uniform(lighting_program, "shadow_cascades"),
+        }
+        view_matrix.invert().unwrap().set_as_uniform(lighting_program, "inverse_view_matrix");
         lighting_program.set_texture(unsafe { c_str!("albedo") }, 1);
         lighting_program.set_texture(unsafe { c_str!("position") }, 2);
         lighting_program.set_texture(unsafe { c_str!("normal") }, 3);
 
         Vector2::new(width as f32, height as f32)
             .set_as_uniform(lighting_program, "resolution");
+        world_time.light_direction().set_as_uniform(lighting_program, "light_dir");
+        world_time.direct_light_color().set_as_uniform(lighting_program, "light_color");
+        world_time.sky_light().set_as_uniform(lighting_program, "sky_light");
+        sky_horizon_color.set_as_uniform(lighting_program, "fog_color");
+        (fog_end * 0.5).set_as_uniform(lighting_program, "fog_start");
+        fog_end.set_as_uniform(lighting_program, "fog_end");
 
         screenquad.draw();
 
@@ -243,9 +358,42 @@ impl Engine {
             ("model_matrix", Box::new(skybox_model_matrix)),
             ("perspective_matrix", Box::new(perspective_matrix)),
             ("view_matrix", Box::new(view_matrix)),
+            ("sky_zenith_color", Box::new(sky_zenith_color)),
+            ("sky_horizon_color", Box::new(sky_horizon_color)),
+            ("sun_dir", Box::new(world_time.sun_direction())),
+            ("light_color", Box::new(world_time.light_color())),
         ];
         self.skybox.read().unwrap().draw(&gl_resources, &geometry_uniforms);
 
+        // Blend translucent blocks over the lit scene, tested against the GBuffer depth
+        let translucent_uniforms: Vec<(&str, Box<dyn Uniform>)> = vec![
+            ("perspective_matrix", Box::new(perspective_matrix)),
+            ("view_matrix", Box::new(view_matrix)),
+            ("time", Box::new(elapsed_time.as_secs_f32())),
+            ("animations", Box::new(animations)),
+            ("sky_light", Box::new(world_time.sky_light())),
+            ("light_dir", Box::new(world_time.light_direction())),
+            ("light_color", Box::new(world_time.direct_light_color())),
+            ("fog_color", Box::new(sky_horizon_color)),
+            ("fog_start", Box::new(fog_end * 0.5)),
+            ("fog_end", Box::new(fog_end)),
+        ];
+        terrain.draw_translucent(&gl_resources, &translucent_uniforms, player.camera.position);
+
+        // Outline and cracks on the targeted block, over translucent blocks so glass can be picked out
+        let overlay_uniforms: Vec<(&str, Box<dyn Uniform>)> = vec![
+            ("perspective_matrix", Box::new(perspective_matrix)),
+            ("view_matrix", Box::new(view_matrix)),
+            ("time", Box::new(elapsed_time.as_secs_f32())),
+        ];
+        self.hud.draw_block_overlays(
+            &gl_resources,
+            &terrain,
+            &overlay_uniforms,
+            player.targeted_block(&terrain),
+            player.mining.crack_stage(),
+        );
+
         composite_fbo.unbind();
 
         /**********************************
@@ -257,12 +405,19 @@ impl Engine {
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         }
 
-        let postprocess_shader = gl_resources.get_shader("postprocess").unwrap();
-        postprocess_shader.use_program();
+        let post_uniforms: Vec<(&str, Box<dyn Uniform>)> = vec![
+            ("time", Box::new(elapsed_time.as_secs_f32())),
+            ("exposure", Box::new(exposure)),
+            ("gamma", Box::new(gamma)),
+        ];
+        if let Some(post_process) = &self.post_process {
+            post_process.draw(&gl_resources, screenquad, composite_fbo, &post_uniforms, camera_in_fluid);
+        }
 
-        composite_fbo.bind_render_textures_to_current_fb(&[("color", 0)]);
-        postprocess_shader.set_texture(unsafe { c_str!("composite_output") }, 0);
+        /* ******************************* *
+         * Render HUD over the final frame *
+         * ******************************* */
 
-        screenquad.draw();
+        self.hud.draw(&gl_resources, width, height);
     }
 }
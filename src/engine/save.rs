@@ -4,16 +4,50 @@ use cgmath::Vector3;
 use json::{object, JsonValue};
 
 use crate::{
+    crafting::RecipeBook,
     entity::EntityTrait,
-    graphics::{resources::GLResources, skybox::Skybox},
-    player::Player,
-    terrain::{generation::TerrainGenConfig, Terrain},
+    graphics::{hud::Hud, resources::GLResources, skybox::Skybox, texture_pack::TexturePack},
+    player::{game_mode::GameMode, inventory::Inventory, Player},
+    terrain::{
+        generation::{TerrainGenConfig, DEFAULT_SEED},
+        Terrain,
+    },
 };
 
-use super::{Engine, EngineState};
+use super::{
+    world_time::WorldTime,
+    worlds::{unix_time, WorldInfo},
+    Engine, EngineSettings, EngineState,
+};
 
 impl Engine {
     pub fn load_from_save(save_path: &str) -> Self {
+        Self::load_from_save_with_settings(save_path, EngineSettings::default())
+    }
+
+    /// Open the world in `world_path`, restoring its save if it has one and otherwise generating it
+    /// from the seed in its metadata
+    pub fn open_world(world_path: &str, settings: EngineSettings) -> Result<Self, String> {
+        let path = std::path::Path::new(world_path);
+        let world = WorldInfo::load(path).ok();
+        if let Some(world) = &world {
+            if !world.is_supported() {
+                return Err(format!(
+                    "{} was saved in format {}, which is newer than this build can read",
+                    world.name, world.format_version
+                ));
+            }
+        }
+        if path.join("savestate.json").exists() {
+            Ok(Self::load_from_save_with_settings(world_path, settings))
+        } else {
+            let seed = world.map(|world| world.seed).unwrap_or(DEFAULT_SEED);
+            Ok(Self::new_world(settings, seed))
+        }
+    }
+
+    /// Restore the world saved in `save_path`, tuned by `settings`
+    pub fn load_from_save_with_settings(save_path: &str, settings: EngineSettings) -> Self {
         let mut save_file_path = std::path::PathBuf::new();
         save_file_path.push(save_path);
         save_file_path.push("savestate.json");
@@ -33,30 +67,71 @@ impl Engine {
             player_orientation_json[1].as_f32().unwrap(),
             player_orientation_json[2].as_f32().unwrap(),
         );
-        let player = Box::new(Player::new(player_position, player_direction));
+        let mut player = Box::new(Player::new(player_position, player_direction));
+        player.apply_settings(&settings);
+        if let (Some(yaw), Some(pitch)) = (player_json["yaw"].as_f32(), player_json["pitch"].as_f32()) {
+            player.camera.set_orientation(yaw, pitch);
+        }
+        if !player_json["inventory"].is_null() {
+            player.inventory = Inventory::from_json(&player_json["inventory"]);
+        }
+        if let Some(health) = player_json["health"].as_f32() {
+            player.health.set_points(health);
+        }
+        if let Some(game_mode) = player_json["game_mode"].as_str().and_then(GameMode::from_name) {
+            player.set_game_mode(game_mode);
+            player.set_flying(player_json["flying"].as_bool().unwrap_or(false));
+        }
+
+        let mut engine_state = EngineState::default();
+        let spawn_point_json = &save_json["spawn_point"];
+        if !spawn_point_json.is_null() {
+            engine_state.spawn_point = Vector3::new(
+                spawn_point_json[0].as_f32().unwrap(),
+                spawn_point_json[1].as_f32().unwrap(),
+                spawn_point_json[2].as_f32().unwrap(),
+            );
+        }
+        let time_json = &save_json["time"];
+        if !time_json.is_null() {
+            engine_state.world_time = WorldTime::new(
+                time_json["day"].as_u64().unwrap_or(0),
+                time_json["time_of_day"].as_f32().unwrap(),
+            );
+        }
 
         let terrain_json = &save_json["terrain"];
-        let terrain = Terrain::load_from_json(terrain_json, TerrainGenConfig::default());
+        let seed = terrain_json["seed"].as_u32().unwrap_or(DEFAULT_SEED);
+        let mut terrain_config = TerrainGenConfig::with_seed(seed);
+        terrain_config.load_features(include_str!("../../assets/features/world_features.json"));
+        let mut terrain = Terrain::load_from_json(terrain_json, terrain_config);
+        terrain.set_loading_radius(settings.loading_radius);
 
         let entities: Vec<Box<dyn EntityTrait>> = Vec::new();
-        let mut terrain_config: TerrainGenConfig = TerrainGenConfig::default();
-        terrain_config.load_features(include_str!("../../assets/features/world_features.json"));
+
+        let recipes = RecipeBook::load(include_str!("../../assets/features/recipes.json")).unwrap();
 
         Self {
             player: Arc::new(RwLock::new(player)),
             terrain: Arc::new(RwLock::new(terrain)),
             //terrain_config: Arc::new(RwLock::new(terrain_config)),
-            entities,
+            entities: Arc::new(RwLock::new(entities)),
             skybox: Arc::new(RwLock::new(Skybox)),
+            recipes: Arc::new(recipes),
 
             event_queue: Arc::new(RwLock::new(Vec::new())),
-            engine_state: Arc::new(RwLock::new(EngineState::default())),
-            
+            engine_state: Arc::new(RwLock::new(engine_state)),
+            render_distance: settings.render_distance.into(),
+            settings: Arc::new(RwLock::new(settings)),
+            texture_pack: Arc::new(RwLock::new(Arc::new(TexturePack::builtin()))),
 
             width: 0.into(),
             height: 0.into(),
-            render_distance: 8.into(),
             gl_resources: Arc::new(RwLock::new(GLResources::new())),
+            ssao_pass: None,
+            shadow_pass: None,
+            post_process: None,
+            hud: Hud::new(),
         }
     }
 
@@ -76,9 +151,27 @@ impl Engine {
             let player_json = object! {
                 "position" : [player.position.x, player.position.y, player.position.z],
                 "orientation" : [player.camera.forward.x, player.camera.forward.y, player.camera.forward.z],
+                "yaw" : player.camera.yaw(),
+                "pitch" : player.camera.pitch(),
+                "inventory" : player.inventory.to_json(),
+                "health" : player.health.points(),
+                "game_mode" : player.game_mode().name(),
+                "flying" : player.is_flying(),
             };
             save_json.insert("player", player_json).unwrap();
         }
+        {
+            let engine_state = self.engine_state.read().unwrap();
+            let spawn_point = engine_state.spawn_point;
+            save_json
+                .insert("spawn_point", json::array![spawn_point.x, spawn_point.y, spawn_point.z])
+                .unwrap();
+            let time_json = object! {
+                "day" : engine_state.world_time.day(),
+                "time_of_day" : engine_state.world_time.time_of_day(),
+            };
+            save_json.insert("time", time_json).unwrap();
+        }
         {
             let terrain = self.terrain.write().unwrap();
             let terrain_json = terrain.to_json();
@@ -86,5 +179,32 @@ impl Engine {
         }
 
         std::fs::write(save_file_path, save_json.dump()).unwrap();
+
+        self.save_world_info(save_path);
+    }
+
+    /// Update `world.json` with the time played since the last save, creating it for worlds saved
+    /// before they had metadata
+    fn save_world_info(&self, save_path: &std::path::Path) {
+        let mut world = WorldInfo::load(save_path).unwrap_or_else(|_| {
+            let id = save_path
+                .file_name()
+                .map(|id| id.to_string_lossy().to_string())
+                .unwrap_or_else(|| "world".to_string());
+            WorldInfo::new(&id, &id, self.seed())
+        });
+        {
+            let mut engine_state = self.engine_state.write().unwrap();
+            let play_time = engine_state.elapsed_time - engine_state.saved_elapsed_time;
+            world.play_time += play_time.as_secs();
+            // Keep the leftover fraction of a second for the next save
+            engine_state.saved_elapsed_time += std::time::Duration::from_secs(play_time.as_secs());
+        }
+        // A world replaced with a new one keeps its name but not its old seed
+        world.seed = self.seed();
+        world.last_played = unix_time();
+        if let Err(e) = world.save(save_path) {
+            println!("Couldn't save world metadata: {}", e);
+        }
     }
 }
@@ -0,0 +1,381 @@
+use json::{object, JsonValue};
+
+use crate::{
+    graphics::{
+        postprocess::PostEffect, shadow::MAX_SHADOW_CASCADES, ssao::SsaoQuality,
+        texture::TextureQuality,
+    },
+    player::{camera::LookSettings, GRAVITY},
+    terrain::DEFAULT_LOADING_RADIUS,
+};
+
+/// Tunables for the engine and the desktop window, loaded from a JSON file where any missing setting
+/// keeps its default
+#[derive(Clone, Debug)]
+pub struct EngineSettings {
+    /// Only read when the window is created
+    pub window_width: i32,
+    /// Only read when the window is created
+    pub window_height: i32,
+    /// Directory the desktop build saves the world to
+    pub save_path: String,
+
+    /// Chunks drawn around the player
+    pub render_distance: isize,
+    /// Chunks kept loaded around the player
+    pub loading_radius: isize,
+
+    /// Walking speed in blocks per second
+    pub move_speed: f32,
+    pub sprint_multiplier: f32,
+    /// Height of the player's eyes above their feet
+    pub player_height: f32,
+    /// Downward acceleration of the player in blocks per second squared
+    pub gravity: f32,
+    pub look: LookSettings,
+
+    /// Real seconds in a full day and night
+    pub day_length: f32,
+
+    /// Ambient occlusion quality, only read when the GL resources are created
+    pub ssao: SsaoQuality,
+    /// Shadows from the sun and moon, only read when the GL resources are created
+    pub shadows: bool,
+    /// Width and height of each shadow map in texels
+    pub shadow_resolution: i32,
+    /// Shadow maps of growing size around the player, the nearest being the sharpest
+    pub shadow_cascades: usize,
+    /// Effects run over the finished frame in order, only read when the GL resources are created
+    pub post_effects: Vec<PostEffect>,
+    /// Directory of the texture pack to draw with instead of the built-in textures, only read when
+    /// the GL resources are created
+    pub texture_pack: Option<String>,
+    /// Filtering of block textures in the distance, only read when the GL resources are created
+    pub texture_quality: TextureQuality,
+    /// Brightness scale applied before tone mapping
+    pub exposure: f32,
+    pub gamma: f32,
+    /// Meshes uploaded to the GPU each frame
+    pub vao_uploads_per_frame: usize,
+    /// Pause between game loop ticks
+    pub tick_sleep_ms: u64,
+    /// Pause between passes of the terrain worker
+    pub worker_sleep_ms: u64,
+}
+
+impl Default for EngineSettings {
+    fn default() -> Self {
+        Self {
+            window_width: 1920,
+            window_height: 900,
+            save_path: "blockcraft".to_string(),
+
+            render_distance: 8,
+            loading_radius: DEFAULT_LOADING_RADIUS,
+
+            move_speed: 4.0,
+            sprint_multiplier: 2.0,
+            player_height: 1.6,
+            gravity: -GRAVITY.y,
+            look: LookSettings::default(),
+
+            day_length: 1200.0,
+
+            ssao: SsaoQuality::default(),
+            shadows: true,
+            shadow_resolution: if cfg!(target_os = "android") { 1024 } else { 2048 },
+            shadow_cascades: if cfg!(target_os = "android") { 1 } else { 2 },
+            post_effects: vec![PostEffect::Gamma, PostEffect::Vignette, PostEffect::Underwater],
+            texture_pack: None,
+            texture_quality: TextureQuality::default(),
+            exposure: 1.0,
+            gamma: 1.0,
+            vao_uploads_per_frame: 2,
+            tick_sleep_ms: 5,
+            worker_sleep_ms: 1,
+        }
+    }
+}
+
+impl EngineSettings {
+    /// Read settings from the JSON file at `path`
+    pub fn load(path: &str) -> Result<Self, String> {
+        let settings_file = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::parse(&settings_file)
+    }
+
+    pub fn parse(settings_json: &str) -> Result<Self, String> {
+        let settings_json = json::parse(settings_json).map_err(|e| e.to_string())?;
+        Self::from_json(&settings_json)
+    }
+
+    pub fn from_json(settings_json: &JsonValue) -> Result<Self, String> {
+        if !settings_json.is_object() {
+            return Err("Settings must be a JSON object".to_string());
+        }
+        let default = Self::default();
+        let settings = Self {
+            window_width: read_number(settings_json, "window_width", default.window_width)?,
+            window_height: read_number(settings_json, "window_height", default.window_height)?,
+            save_path: match &settings_json["save_path"] {
+                JsonValue::Null => default.save_path,
+                value => value
+                    .as_str()
+                    .ok_or("Setting \"save_path\" must be a string")?
+                    .to_string(),
+            },
+
+            render_distance: read_number(settings_json, "render_distance", default.render_distance)?,
+            loading_radius: read_number(settings_json, "loading_radius", default.loading_radius)?,
+
+            move_speed: read_number(settings_json, "move_speed", default.move_speed)?,
+            sprint_multiplier: read_number(settings_json, "sprint_multiplier", default.sprint_multiplier)?,
+            player_height: read_number(settings_json, "player_height", default.player_height)?,
+            gravity: read_number(settings_json, "gravity", default.gravity)?,
+            look: LookSettings {
+                sensitivity: read_number(settings_json, "look_sensitivity", default.look.sensitivity)?,
+                invert_y: match &settings_json["invert_y"] {
+                    JsonValue::Null => default.look.invert_y,
+                    value => value.as_bool().ok_or("Setting \"invert_y\" must be true or false")?,
+                },
+                smoothing: read_number(settings_json, "look_smoothing", default.look.smoothing)?,
+            },
+
+            day_length: read_number(settings_json, "day_length", default.day_length)?,
+
+            ssao: match &settings_json["ssao"] {
+                JsonValue::Null => default.ssao,
+                value => value
+                    .as_str()
+                    .and_then(SsaoQuality::from_name)
+                    .ok_or("Setting \"ssao\" must be \"off\", \"low\" or \"high\"")?,
+            },
+            shadows: match &settings_json["shadows"] {
+                JsonValue::Null => default.shadows,
+                value => value.as_bool().ok_or("Setting \"shadows\" must be true or false")?,
+            },
+            shadow_resolution: read_number(settings_json, "shadow_resolution", default.shadow_resolution)?,
+            shadow_cascades: read_number(settings_json, "shadow_cascades", default.shadow_cascades)?,
+            post_effects: match &settings_json["post_effects"] {
+                JsonValue::Null => default.post_effects,
+                JsonValue::Array(effects) => effects
+                    .iter()
+                    .map(|effect| {
+                        effect
+                            .as_str()
+                            .and_then(PostEffect::from_name)
+                            .ok_or_else(|| format!("Unknown post effect {}", effect))
+                    })
+                    .collect::<Result<Vec<PostEffect>, String>>()?,
+                _ => return Err("Setting \"post_effects\" must be a list of effect names".to_string()),
+            },
+            texture_pack: match &settings_json["texture_pack"] {
+                JsonValue::Null => default.texture_pack,
+                value => Some(
+                    value
+                        .as_str()
+                        .ok_or("Setting \"texture_pack\" must be a directory path")?
+                        .to_string(),
+                ),
+            },
+            texture_quality: match &settings_json["texture_quality"] {
+                JsonValue::Null => default.texture_quality,
+                value => value
+                    .as_str()
+                    .and_then(TextureQuality::from_name)
+                    .ok_or("Setting \"texture_quality\" must be \"nearest\", \"mipmapped\" or \"anisotropic\"")?,
+            },
+            exposure: read_number(settings_json, "exposure", default.exposure)?,
+            gamma: read_number(settings_json, "gamma", default.gamma)?,
+            vao_uploads_per_frame: read_number(
+                settings_json,
+                "vao_uploads_per_frame",
+                default.vao_uploads_per_frame,
+            )?,
+            tick_sleep_ms: read_number(settings_json, "tick_sleep_ms", default.tick_sleep_ms)?,
+            worker_sleep_ms: read_number(settings_json, "worker_sleep_ms", default.worker_sleep_ms)?,
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        object! {
+            "window_width" : self.window_width,
+            "window_height" : self.window_height,
+            "save_path" : self.save_path.as_str(),
+            "render_distance" : self.render_distance,
+            "loading_radius" : self.loading_radius,
+            "move_speed" : self.move_speed,
+            "sprint_multiplier" : self.sprint_multiplier,
+            "player_height" : self.player_height,
+            "gravity" : self.gravity,
+            "look_sensitivity" : self.look.sensitivity,
+            "invert_y" : self.look.invert_y,
+            "look_smoothing" : self.look.smoothing,
+            "day_length" : self.day_length,
+            "ssao" : self.ssao.name(),
+            "shadows" : self.shadows,
+            "shadow_resolution" : self.shadow_resolution,
+            "shadow_cascades" : self.shadow_cascades,
+            "post_effects" : self.post_effects.iter().map(|effect| effect.name()).collect::<Vec<&str>>(),
+            "texture_pack" : self.texture_pack.as_deref(),
+            "texture_quality" : self.texture_quality.name(),
+            "exposure" : self.exposure,
+            "gamma" : self.gamma,
+            "vao_uploads_per_frame" : self.vao_uploads_per_frame,
+            "tick_sleep_ms" : self.tick_sleep_ms,
+            "worker_sleep_ms" : self.worker_sleep_ms,
+        }
+    }
+
+    /// Check every setting is within a range the engine can run with
+    pub fn validate(&self) -> Result<(), String> {
+        check_range("window_width", self.window_width, 1, 16384)?;
+        check_range("window_height", self.window_height, 1, 16384)?;
+        if self.save_path.is_empty() {
+            return Err("Setting \"save_path\" can't be empty".to_string());
+        }
+        check_range("render_distance", self.render_distance, 1, 32)?;
+        check_range("loading_radius", self.loading_radius, 1, 32)?;
+        check_range("move_speed", self.move_speed, 0.1, 100.0)?;
+        check_range("sprint_multiplier", self.sprint_multiplier, 1.0, 10.0)?;
+        check_range("player_height", self.player_height, 0.5, 1.6)?;
+        check_range("gravity", self.gravity, 0.1, 100.0)?;
+        check_range("look_sensitivity", self.look.sensitivity, 0.0001, 1.0)?;
+        check_range("look_smoothing", self.look.smoothing, 0.0, 1.0)?;
+        check_range("day_length", self.day_length, 10.0, 86400.0)?;
+        check_range("shadow_resolution", self.shadow_resolution, 256, 4096)?;
+        if self.shadow_resolution.count_ones() != 1 {
+            return Err("Setting \"shadow_resolution\" must be a power of two".to_string());
+        }
+        check_range("shadow_cascades", self.shadow_cascades, 1, MAX_SHADOW_CASCADES)?;
+        if self.texture_pack.as_deref() == Some("") {
+            return Err("Setting \"texture_pack\" can't be empty".to_string());
+        }
+        check_range("exposure", self.exposure, 0.1, 10.0)?;
+        check_range("gamma", self.gamma, 0.5, 3.0)?;
+        check_range("vao_uploads_per_frame", self.vao_uploads_per_frame, 1, 64)?;
+        check_range("tick_sleep_ms", self.tick_sleep_ms, 0, 1000)?;
+        check_range("worker_sleep_ms", self.worker_sleep_ms, 0, 1000)?;
+        Ok(())
+    }
+}
+
+/// Conversion from a JSON value for the numeric types settings are stored as
+trait SettingNumber: Sized + Copy + PartialOrd + std::fmt::Display {
+    fn from_json(value: &JsonValue) -> Option<Self>;
+}
+
+impl SettingNumber for i32 {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        value.as_i32()
+    }
+}
+
+impl SettingNumber for isize {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        value.as_isize()
+    }
+}
+
+impl SettingNumber for usize {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        value.as_usize()
+    }
+}
+
+impl SettingNumber for u64 {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        value.as_u64()
+    }
+}
+
+impl SettingNumber for f32 {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        value.as_f32()
+    }
+}
+
+fn read_number<T: SettingNumber>(settings_json: &JsonValue, key: &str, default: T) -> Result<T, String> {
+    let value = &settings_json[key];
+    if value.is_null() {
+        return Ok(default);
+    }
+    T::from_json(value).ok_or_else(|| format!("Setting \"{}\" must be a number, got {}", key, value))
+}
+
+fn check_range<T: SettingNumber>(key: &str, value: T, min: T, max: T) -> Result<(), String> {
+    if value < min || value > max {
+        return Err(format!(
+            "Setting \"{}\" is {}, it must be between {} and {}",
+            key, value, min, max
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_settings_keep_their_defaults() {
+        let settings = EngineSettings::parse(r#"{ "render_distance": 12 }"#).unwrap();
+        let default = EngineSettings::default();
+        assert_eq!(settings.render_distance, 12);
+        assert_eq!(settings.loading_radius, default.loading_radius);
+        assert_eq!(settings.gravity, default.gravity);
+        assert_eq!(settings.save_path, default.save_path);
+    }
+
+    #[test]
+    fn settings_round_trip_through_json() {
+        let settings = EngineSettings::parse(r#"{ "move_speed": 7.5, "invert_y": true, "ssao": "high" }"#).unwrap();
+        let reparsed = EngineSettings::from_json(&settings.to_json()).unwrap();
+        assert_eq!(reparsed.move_speed, 7.5);
+        assert!(reparsed.look.invert_y);
+        assert_eq!(reparsed.ssao, SsaoQuality::High);
+    }
+
+    #[test]
+    fn malformed_settings_are_rejected() {
+        assert!(EngineSettings::parse("not json").is_err());
+        assert!(EngineSettings::parse("[1, 2]").is_err());
+        assert!(EngineSettings::parse(r#"{ "render_distance": "far" }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "invert_y": 1 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "ssao": "ultra" }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "post_effects": ["vignette", "sparkles"] }"#).is_err());
+    }
+
+    #[test]
+    fn out_of_range_settings_are_rejected() {
+        assert!(EngineSettings::parse(r#"{ "render_distance": 0 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "render_distance": 33 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "shadow_resolution": 1000 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "save_path": "" }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "texture_pack": "" }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "render_distance": 32, "gamma": 3.0 }"#).is_ok());
+    }
+
+    #[test]
+    fn texture_quality_round_trips_through_json() {
+        for quality in [TextureQuality::Nearest, TextureQuality::Mipmapped, TextureQuality::Anisotropic] {
+            let settings = EngineSettings {
+                texture_quality: quality,
+                ..EngineSettings::default()
+            };
+            let json = settings.to_json();
+            assert_eq!(json["texture_quality"], quality.name());
+            assert_eq!(EngineSettings::from_json(&json).unwrap().texture_quality, quality);
+        }
+        assert!(EngineSettings::parse(r#"{ "texture_quality": "trilinear" }"#).is_err());
+    }
+
+    #[test]
+    fn gravity_must_be_positive() {
+        assert!(EngineSettings::parse(r#"{ "gravity": 0 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "gravity": -9.8 }"#).is_err());
+        assert!(EngineSettings::parse(r#"{ "gravity": 9.8 }"#).is_ok());
+    }
+}
@@ -1,13 +1,23 @@
 use std::{sync::{Arc, RwLock}, time::Duration};
-use crate::{graphics::resources::GLResources, terrain::{chunk::Chunk, generation::{terraingen, TerrainGenConfig}, Terrain}};
+use crate::{engine::EngineSettings, graphics::{resources::GLResources, texture_pack::TexturePack}, terrain::{chunk::Chunk, generation::{terraingen, TerrainGenConfig}, Terrain}};
 
 
 pub trait EngineWorker {
-    fn start_thread(&self, gl_resources: Arc<RwLock<GLResources>>);
+    fn start_thread(
+        &self,
+        gl_resources: Arc<RwLock<GLResources>>,
+        settings: Arc<RwLock<EngineSettings>>,
+        texture_pack: Arc<RwLock<Arc<TexturePack>>>,
+    );
 }
 
 impl EngineWorker for Arc<RwLock<Terrain>> {
-    fn start_thread(&self, gl_resources: Arc<RwLock<GLResources>>) {
+    fn start_thread(
+        &self,
+        gl_resources: Arc<RwLock<GLResources>>,
+        settings: Arc<RwLock<EngineSettings>>,
+        texture_pack: Arc<RwLock<Arc<TexturePack>>>,
+    ) {
         let terrain = self.clone();
         std::thread::spawn(move || {
             loop {
@@ -49,10 +59,14 @@ impl EngineWorker for Arc<RwLock<Terrain>> {
                 {
                     let mut terrain = terrain.write().unwrap();
                     terrain.place_features();
-                    terrain.update_meshes(&mut gl_resources.write().unwrap());
+                    // Read under the terrain lock, so a pack swapped in after this is followed by a
+                    // rebuild of every mesh
+                    let texture_pack = texture_pack.read().unwrap().clone();
+                    terrain.update_meshes(&mut gl_resources.write().unwrap(), &texture_pack);
                 }
 
-                std::thread::sleep(Duration::from_millis(1));
+                let worker_sleep_ms = settings.read().unwrap().worker_sleep_ms;
+                std::thread::sleep(Duration::from_millis(worker_sleep_ms));
             }
         });
     }
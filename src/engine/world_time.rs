@@ -0,0 +1,128 @@
+use cgmath::{InnerSpace, Vector3};
+
+/// Time of day a new world starts at, shortly after sunrise
+pub const DEFAULT_TIME_OF_DAY: f32 = 0.3;
+
+/// Light level the sky falls to at midnight, as a fraction of daylight
+const NIGHT_SKY_LIGHT: f32 = 0.2;
+
+/// Height of the sun or moon below which its direct light fades out
+const HORIZON_FADE: f32 = 0.08;
+
+const DAY_LIGHT_COLOR: Vector3<f32> = Vector3::new(1.0, 0.97, 0.9);
+const SUNSET_LIGHT_COLOR: Vector3<f32> = Vector3::new(1.0, 0.6, 0.35);
+const MOON_LIGHT_COLOR: Vector3<f32> = Vector3::new(0.35, 0.4, 0.6);
+
+const DAY_ZENITH_COLOR: Vector3<f32> = Vector3::new(0.4, 0.6, 1.0);
+const DAY_HORIZON_COLOR: Vector3<f32> = Vector3::new(0.75, 0.85, 1.0);
+const SUNSET_HORIZON_COLOR: Vector3<f32> = Vector3::new(1.0, 0.55, 0.3);
+const NIGHT_ZENITH_COLOR: Vector3<f32> = Vector3::new(0.01, 0.02, 0.06);
+const NIGHT_HORIZON_COLOR: Vector3<f32> = Vector3::new(0.05, 0.06, 0.12);
+
+/// Position in the day/night cycle, where 0 is midnight, 0.25 sunrise, 0.5 noon and 0.75 sunset
+#[derive(Clone, Copy, Debug)]
+pub struct WorldTime {
+    day: u64,
+    time_of_day: f32,
+}
+
+impl Default for WorldTime {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            time_of_day: DEFAULT_TIME_OF_DAY,
+        }
+    }
+}
+
+impl WorldTime {
+    pub fn new(day: u64, time_of_day: f32) -> Self {
+        let mut world_time = Self { day, time_of_day: 0.0 };
+        world_time.set_time_of_day(time_of_day);
+        world_time
+    }
+
+    /// Days that have passed since the world was created
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    pub fn time_of_day(&self) -> f32 {
+        self.time_of_day
+    }
+
+    /// Jump to `time_of_day` within the current day, wrapping values outside 0 to 1
+    pub fn set_time_of_day(&mut self, time_of_day: f32) {
+        self.time_of_day = time_of_day.rem_euclid(1.0);
+    }
+
+    /// Move time forward by `delta_time` seconds of a day lasting `day_length` seconds
+    pub fn advance(&mut self, delta_time: f32, day_length: f32) {
+        let time_of_day = self.time_of_day + delta_time / day_length;
+        self.day += time_of_day.floor() as u64;
+        self.time_of_day = time_of_day.fract();
+    }
+
+    /// Unit vector pointing towards the sun, which rises in +X and sets in -X
+    pub fn sun_direction(&self) -> Vector3<f32> {
+        let angle = (self.time_of_day - 0.25) * std::f32::consts::TAU;
+        // Tilt the sun's path so noon light doesn't come straight down
+        Vector3::new(angle.cos(), angle.sin(), 0.3).normalize()
+    }
+
+    pub fn moon_direction(&self) -> Vector3<f32> {
+        let sun_direction = self.sun_direction();
+        Vector3::new(-sun_direction.x, -sun_direction.y, sun_direction.z)
+    }
+
+    /// How much of the day's light is left, 1 while the sun is well above the horizon and 0 at night
+    pub fn daylight(&self) -> f32 {
+        smoothstep(-0.1, 0.2, self.sun_direction().y)
+    }
+
+    /// Direction of whichever of the sun and moon is lighting the world. This flips from one to the
+    /// other at the horizon, where `direct_light_color` has faded to nothing.
+    pub fn light_direction(&self) -> Vector3<f32> {
+        if self.sun_direction().y >= 0.0 {
+            self.sun_direction()
+        } else {
+            self.moon_direction()
+        }
+    }
+
+    pub fn light_color(&self) -> Vector3<f32> {
+        let sun_color = lerp(SUNSET_LIGHT_COLOR, DAY_LIGHT_COLOR, smoothstep(0.0, 0.3, self.sun_direction().y));
+        lerp(MOON_LIGHT_COLOR, sun_color, self.daylight())
+    }
+
+    /// Color of the light cast along `light_direction`, fading out as the sun or moon meets the horizon
+    /// so the switch between them can't be seen
+    pub fn direct_light_color(&self) -> Vector3<f32> {
+        self.light_color() * smoothstep(0.0, HORIZON_FADE, self.sun_direction().y.abs())
+    }
+
+    /// Colors at the top of the sky and at the horizon, with the horizon reddening around sunrise and sunset
+    pub fn sky_colors(&self) -> (Vector3<f32>, Vector3<f32>) {
+        let daylight = self.daylight();
+        let sunset = 1.0 - (self.sun_direction().y.abs() / 0.25).min(1.0);
+        let day_horizon = lerp(DAY_HORIZON_COLOR, SUNSET_HORIZON_COLOR, sunset);
+        (
+            lerp(NIGHT_ZENITH_COLOR, DAY_ZENITH_COLOR, daylight),
+            lerp(NIGHT_HORIZON_COLOR, day_horizon, daylight.max(sunset * 0.5)),
+        )
+    }
+
+    /// Multiplier for the sunlight stored in chunks, dimming it at night
+    pub fn sky_light(&self) -> f32 {
+        NIGHT_SKY_LIGHT + (1.0 - NIGHT_SKY_LIGHT) * self.daylight()
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(from: Vector3<f32>, to: Vector3<f32>, t: f32) -> Vector3<f32> {
+    from + (to - from) * t
+}
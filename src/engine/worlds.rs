@@ -0,0 +1,298 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use json::{object, JsonValue};
+
+/// Bumped whenever the save layout changes in a way older builds can't read
+pub const WORLD_FORMAT_VERSION: u32 = 1;
+
+const WORLD_METADATA_FILE: &str = "world.json";
+
+/// Metadata kept in `world.json` next to a world's save
+#[derive(Clone, Debug)]
+pub struct WorldInfo {
+    /// Name of the world's directory, which stays the same when the world is renamed
+    pub id: String,
+    pub name: String,
+    pub seed: u32,
+    /// Seconds since the Unix epoch
+    pub created: u64,
+    /// Seconds since the Unix epoch
+    pub last_played: u64,
+    /// Seconds spent in the world while it wasn't paused
+    pub play_time: u64,
+    pub format_version: u32,
+}
+
+impl WorldInfo {
+    pub fn new(id: &str, name: &str, seed: u32) -> Self {
+        let now = unix_time();
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            seed,
+            created: now,
+            last_played: now,
+            play_time: 0,
+            format_version: WORLD_FORMAT_VERSION,
+        }
+    }
+
+    /// Read the metadata of the world in `world_path`
+    pub fn load(world_path: &Path) -> Result<Self, String> {
+        let metadata_path = world_path.join(WORLD_METADATA_FILE);
+        let metadata =
+            std::fs::read_to_string(&metadata_path).map_err(|e| format!("{:?}: {}", metadata_path, e))?;
+        let metadata = json::parse(&metadata).map_err(|e| format!("{:?}: {}", metadata_path, e))?;
+        let id = world_path
+            .file_name()
+            .map(|id| id.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(Self {
+            name: metadata["name"].as_str().unwrap_or(&id).to_string(),
+            seed: metadata["seed"].as_u32().ok_or("World metadata is missing the seed")?,
+            created: metadata["created"].as_u64().unwrap_or(0),
+            last_played: metadata["last_played"].as_u64().unwrap_or(0),
+            play_time: metadata["play_time"].as_u64().unwrap_or(0),
+            format_version: metadata["format_version"].as_u32().unwrap_or(WORLD_FORMAT_VERSION),
+            id,
+        })
+    }
+
+    pub fn save(&self, world_path: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(world_path).map_err(|e| format!("{:?}: {}", world_path, e))?;
+        let metadata_path = world_path.join(WORLD_METADATA_FILE);
+        std::fs::write(&metadata_path, self.to_json().pretty(4))
+            .map_err(|e| format!("{:?}: {}", metadata_path, e))
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        object! {
+            "id" : self.id.as_str(),
+            "name" : self.name.as_str(),
+            "seed" : self.seed,
+            "created" : self.created,
+            "last_played" : self.last_played,
+            "play_time" : self.play_time,
+            "format_version" : self.format_version,
+        }
+    }
+
+    /// Whether this build can load the world
+    pub fn is_supported(&self) -> bool {
+        self.format_version <= WORLD_FORMAT_VERSION
+    }
+}
+
+/// The worlds kept as directories inside a single folder
+pub struct WorldList {
+    root: PathBuf,
+}
+
+impl WorldList {
+    pub fn new(root: &str) -> Self {
+        Self { root: PathBuf::from(root) }
+    }
+
+    pub fn path(&self, id: &str) -> PathBuf {
+        self.root.join(id)
+    }
+
+    /// Every world with readable metadata, most recently played first
+    pub fn list(&self) -> Vec<WorldInfo> {
+        let mut worlds: Vec<WorldInfo> = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|entry| entry.path().join(WORLD_METADATA_FILE).exists())
+                .filter_map(|entry| WorldInfo::load(&entry.path()).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        worlds.sort_by_key(|world| std::cmp::Reverse(world.last_played));
+        worlds
+    }
+
+    pub fn get(&self, id: &str) -> Result<WorldInfo, String> {
+        WorldInfo::load(&self.path(self.checked_id(id)?))
+    }
+
+    /// Make an empty world called `name`, which generates its terrain the first time it is opened
+    pub fn create(&self, name: &str, seed: u32) -> Result<WorldInfo, String> {
+        let world = WorldInfo::new(&self.unused_id(name), name, seed);
+        world.save(&self.path(&world.id))?;
+        Ok(world)
+    }
+
+    /// Change the name a world is shown with, leaving its directory where it is
+    pub fn rename(&self, id: &str, name: &str) -> Result<WorldInfo, String> {
+        let mut world = self.get(id)?;
+        world.name = name.to_string();
+        world.save(&self.path(id))?;
+        Ok(world)
+    }
+
+    /// Copy a world and everything saved in it to a new world called `name`
+    pub fn duplicate(&self, id: &str, name: &str) -> Result<WorldInfo, String> {
+        let mut world = self.get(id)?;
+        world.id = self.unused_id(name);
+        world.name = name.to_string();
+        world.created = unix_time();
+        copy_dir(&self.path(id), &self.path(&world.id)).map_err(|e| e.to_string())?;
+        world.save(&self.path(&world.id))?;
+        Ok(world)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let world_path = self.path(self.checked_id(id)?);
+        // Only remove directories which really are worlds
+        if !world_path.join(WORLD_METADATA_FILE).exists() {
+            return Err(format!("There is no world called {}", id));
+        }
+        std::fs::remove_dir_all(&world_path).map_err(|e| format!("{:?}: {}", world_path, e))
+    }
+
+    /// Stop IDs from reaching outside the worlds folder
+    fn checked_id<'a>(&self, id: &'a str) -> Result<&'a str, String> {
+        if id.is_empty() || id.contains(['/', '\\']) || id == "." || id == ".." {
+            return Err(format!("{} isn't a valid world ID", id));
+        }
+        Ok(id)
+    }
+
+    /// A directory name made from `name` which no other world is using
+    fn unused_id(&self, name: &str) -> String {
+        let base: String = name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let base = if base.is_empty() { "world".to_string() } else { base };
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.path(&id).exists() {
+            id = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        id
+    }
+}
+
+pub(crate) fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or(0)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A worlds folder in the system temp directory, removed again when the test is done
+    struct TempWorlds {
+        root: PathBuf,
+        worlds: WorldList,
+    }
+
+    impl TempWorlds {
+        fn new(test_name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("voxel-worlds-{}-{}", std::process::id(), test_name));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(&root).unwrap();
+            let worlds = WorldList::new(root.to_str().unwrap());
+            Self { root, worlds }
+        }
+    }
+
+    impl Drop for TempWorlds {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn worlds_are_listed_most_recently_played_first() {
+        let temp = TempWorlds::new("list");
+        let older = temp.worlds.create("Older", 1).unwrap();
+        let mut newer = temp.worlds.create("Newer", 2).unwrap();
+        newer.last_played = older.last_played + 60;
+        newer.save(&temp.worlds.path(&newer.id)).unwrap();
+
+        let ids: Vec<String> = temp.worlds.list().into_iter().map(|world| world.id).collect();
+        assert_eq!(ids, vec![newer.id, older.id]);
+    }
+
+    #[test]
+    fn created_worlds_get_unused_ids() {
+        let temp = TempWorlds::new("create");
+        assert_eq!(temp.worlds.create("My World", 1).unwrap().id, "my_world");
+        assert_eq!(temp.worlds.create("My World", 1).unwrap().id, "my_world_2");
+        assert_eq!(temp.worlds.create("  ", 1).unwrap().id, "world");
+    }
+
+    #[test]
+    fn renaming_keeps_the_id() {
+        let temp = TempWorlds::new("rename");
+        let world = temp.worlds.create("Before", 7).unwrap();
+        let renamed = temp.worlds.rename(&world.id, "After").unwrap();
+        assert_eq!(renamed.id, world.id);
+        let loaded = temp.worlds.get(&world.id).unwrap();
+        assert_eq!(loaded.name, "After");
+        assert_eq!(loaded.seed, 7);
+    }
+
+    #[test]
+    fn duplicates_copy_the_save_to_a_fresh_id() {
+        let temp = TempWorlds::new("duplicate");
+        let world = temp.worlds.create("Copied", 3).unwrap();
+        let save_dir = temp.worlds.path(&world.id).join("region");
+        std::fs::create_dir_all(&save_dir).unwrap();
+        std::fs::write(save_dir.join("chunk.json"), "{}").unwrap();
+
+        let copy = temp.worlds.duplicate(&world.id, "Copied").unwrap();
+        assert_eq!(copy.id, "copied_2");
+        assert_eq!(copy.seed, 3);
+        assert!(temp.worlds.path(&copy.id).join("region").join("chunk.json").exists());
+        assert_eq!(temp.worlds.get(&world.id).unwrap().name, "Copied");
+    }
+
+    #[test]
+    fn delete_only_removes_worlds() {
+        let temp = TempWorlds::new("delete");
+        let world = temp.worlds.create("Doomed", 1).unwrap();
+        std::fs::create_dir_all(temp.root.join("not_a_world")).unwrap();
+
+        assert!(temp.worlds.delete("not_a_world").is_err());
+        assert!(temp.root.join("not_a_world").exists());
+        temp.worlds.delete(&world.id).unwrap();
+        assert!(!temp.worlds.path(&world.id).exists());
+        assert!(temp.worlds.delete(&world.id).is_err());
+    }
+
+    #[test]
+    fn ids_outside_the_worlds_folder_are_rejected() {
+        let temp = TempWorlds::new("checked_id");
+        for id in ["..", ".", "a/b", "a\\b", ""] {
+            assert!(temp.worlds.checked_id(id).is_err(), "{:?} was accepted", id);
+            assert!(temp.worlds.delete(id).is_err());
+        }
+        assert_eq!(temp.worlds.checked_id("world_2"), Ok("world_2"));
+    }
+}
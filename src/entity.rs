@@ -1,6 +1,14 @@
 use crate::{
     graphics::resources::GLRenderable,
     physics::{collision::Collider, physics_update::PhysicsUpdate},
+    terrain::Terrain,
 };
 
-pub trait EntityTrait: GLRenderable + PhysicsUpdate + Collider + Sync + Send {}
+pub(crate) mod falling_block;
+
+pub trait EntityTrait: GLRenderable + PhysicsUpdate + Collider + Sync + Send {
+    /// Called once per tick after collisions are resolved. Returns false once the entity should be removed
+    fn tick(&mut self, _terrain: &mut Terrain) -> bool {
+        true
+    }
+}
@@ -0,0 +1,175 @@
+use cgmath::Zero;
+pub(crate) use cgmath::{Matrix4, Vector3};
+
+use crate::{
+    c_str,
+    entity::EntityTrait,
+    graphics::{
+        mesh::block_drop_vertices,
+        resources::{GLRenderable, GLResources},
+        texture_pack::TexturePack,
+        uniform::Uniform,
+    },
+    physics::{
+        collision::{Collider, Rect3},
+        physics_update::PhysicsUpdate,
+        vectormath::Vec3Direction,
+    },
+    terrain::{block::BLOCKS, BlockWorldPos, Terrain},
+};
+
+/// A gravity-affected block which lost its support, falls until it lands and then becomes a block again
+pub struct FallingBlock {
+    pub block_id: usize,
+    pub position: Vector3<f32>,
+    /// Downward acceleration in blocks per second squared
+    gravity: f32,
+    /// Set while the block is over an unloaded chunk, where it hangs in place until the chunk is back
+    /// since it has nothing to land on
+    waiting_for_chunk: bool,
+
+    // Physics properties
+    velocity: Vector3<f32>,
+    acceleration: Vector3<f32>,
+    movement_delta: Vector3<f32>,
+    collider: Rect3,
+    grounded: bool,
+}
+
+impl FallingBlock {
+    pub fn new(block_id: usize, world_pos: BlockWorldPos, gravity: f32) -> FallingBlock {
+        FallingBlock {
+            block_id,
+            position: Vector3::new(world_pos.x as f32, world_pos.y as f32, world_pos.z as f32),
+            gravity,
+            waiting_for_chunk: false,
+
+            // Slightly narrower than a block so it doesn't snag on its neighbors while falling
+            collider: Rect3::new(Vector3::new(0.01, 0.0, 0.01), Vector3::new(0.98, 1.0, 0.98)),
+            velocity: Vector3::zero(),
+            acceleration: Vector3::zero(),
+            movement_delta: Vector3::zero(),
+            grounded: false,
+        }
+    }
+
+    /// Block the falling block's position rounds to
+    fn block_pos(&self) -> BlockWorldPos {
+        BlockWorldPos::new(
+            self.position.x.round() as isize,
+            self.position.y.round() as isize,
+            self.position.z.round() as isize,
+        )
+    }
+
+    fn vao_name(&self) -> String {
+        format!("item_{}", self.block_id)
+    }
+}
+
+impl GLRenderable for FallingBlock {
+    fn init_gl_resources(&self, gl_resources: &mut GLResources, texture_pack: &TexturePack) {
+        let name = self.vao_name();
+        if gl_resources.get_vao(&name).is_none() {
+            let verts = Box::new(block_drop_vertices(&BLOCKS[self.block_id], texture_pack));
+            gl_resources.update_vao_buffer(name, verts);
+        }
+    }
+
+    fn draw(&self, gl_resources: &GLResources, uniforms: &[(&str, Box<dyn Uniform>)]) {
+        let model_matrix = Matrix4::from_translation(self.position);
+
+        let shader = gl_resources.get_shader("terrain").unwrap();
+        let texture = gl_resources.get_texture("terrain").unwrap();
+
+        texture.use_as_framebuffer_texture(0);
+
+        shader.use_program();
+        for (name, uniform) in uniforms.iter() {
+            uniform.set_as_uniform(shader, name);
+        }
+        shader.set_mat4(unsafe { c_str!("model_matrix") }, &model_matrix);
+        shader.set_texture(unsafe { c_str!("texture_map") }, 0);
+
+        if let Some(vao) = gl_resources.get_vao(&self.vao_name()) {
+            vao.draw();
+        }
+    }
+}
+
+impl Collider for FallingBlock {
+    fn bounding_box(&self) -> Rect3 {
+        let mut col_corrected = self.collider.clone();
+        col_corrected.pos += self.position;
+        col_corrected
+    }
+
+    fn movement_delta(&self) -> Vector3<f32> {
+        self.movement_delta
+    }
+
+    fn correct_position_axis(&mut self, axis: Vec3Direction, overlap: f32) {
+        match axis {
+            Vec3Direction::X => {
+                self.position.x += overlap;
+            }
+            Vec3Direction::Y => {
+                self.position.y += overlap;
+                if overlap.abs() > 0.0 {
+                    self.velocity.y = 0f32;
+                    if overlap > 0.0 {
+                        self.grounded = true;
+                    }
+                }
+            }
+            Vec3Direction::Z => {
+                self.position.z += overlap;
+            }
+        }
+    }
+
+    fn has_collider(&self) -> bool {
+        true
+    }
+}
+
+impl PhysicsUpdate for FallingBlock {
+    fn update_physics(&mut self, delta_time: f32) {
+        if self.waiting_for_chunk {
+            self.velocity = Vector3::zero();
+            self.movement_delta = Vector3::zero();
+            return;
+        }
+        if !self.grounded {
+            self.acceleration.y = -self.gravity;
+        }
+        self.velocity += self.acceleration * delta_time;
+
+        self.movement_delta = delta_time
+            * Vector3 {
+                x: 0.0,
+                y: self.velocity.y,
+                z: 0.0,
+            };
+    }
+
+    fn translate_relative(&mut self, translation: Vector3<f32>) {
+        self.position += translation;
+    }
+}
+
+impl EntityTrait for FallingBlock {
+    fn tick(&mut self, terrain: &mut Terrain) -> bool {
+        let block_pos = self.block_pos();
+        self.waiting_for_chunk = !terrain.column_loaded_at_world_pos(&block_pos);
+        if self.waiting_for_chunk {
+            return true;
+        }
+        if !self.grounded {
+            // Blocks which fall out of the world are lost
+            return self.position.y > 0.0;
+        }
+        terrain.land_falling_block(&block_pos, self.block_id);
+        false
+    }
+}
@@ -1,11 +1,18 @@
+pub(crate) mod atlas;
 pub(crate) mod depthbuffer;
+pub(crate) mod font;
 pub(crate) mod framebuffer;
+pub(crate) mod hud;
 pub(crate) mod mesh;
+pub(crate) mod postprocess;
 pub(crate) mod resources;
 pub(crate) mod shader;
+pub(crate) mod shadow;
 pub(crate) mod skybox;
 pub(crate) mod source;
+pub(crate) mod ssao;
 pub(crate) mod texture;
+pub(crate) mod texture_pack;
 pub(crate) mod uniform;
 pub(crate) mod vao;
 pub(crate) mod vbo;
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use cgmath::Vector3;
+use image::{imageops, Rgba, RgbaImage};
+
+/// Name of the tile drawn for textures the atlas doesn't contain
+pub(crate) const PLACEHOLDER_TEXTURE: &str = "missing";
+
+/// Animated textures an atlas can hold, limited by the size of the shaders' uniform array
+pub const MAX_ANIMATIONS: usize = 32;
+
+/// Mip levels an atlas is built with at most, including the full size image
+pub const MAX_MIP_LEVELS: u32 = 4;
+
+/// A texture to be stitched into an atlas
+pub struct AtlasTile {
+    /// Every frame of the texture, stacked from the first at the top
+    pub image: RgbaImage,
+    pub frames: u32,
+    /// Seconds each frame is shown for
+    pub frame_time: f32,
+}
+
+impl AtlasTile {
+    pub fn still(image: RgbaImage) -> Self {
+        Self {
+            image,
+            frames: 1,
+            frame_time: 0.0,
+        }
+    }
+
+    /// A texture cycling through the `frames` equal parts of `strip`, from top to bottom
+    pub fn animated(strip: RgbaImage, frames: u32, frame_time: f32) -> Result<Self, String> {
+        if frames == 0 || !strip.height().is_multiple_of(frames) {
+            return Err(format!(
+                "A strip {} pixels high can't be split into {} frames",
+                strip.height(),
+                frames
+            ));
+        }
+        if frame_time <= 0.0 {
+            return Err("Frame time must be above 0".to_string());
+        }
+        Ok(Self {
+            image: strip,
+            frames,
+            frame_time,
+        })
+    }
+
+    fn frame_height(&self) -> u32 {
+        self.image.height() / self.frames
+    }
+}
+
+/// Where a texture was placed in the atlas
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AtlasRegion {
+    /// Area of the texture's first frame as (u0, v0, u1, v1), with v pointing up as the image is
+    /// flipped on upload
+    pub uv: [f32; 4],
+    /// Position of the texture's animation in the atlas's table plus one, 0 for still textures
+    pub animation: i32,
+}
+
+/// Named block textures packed into a single image
+pub struct TextureAtlas {
+    /// The full size image followed by its smaller mip levels
+    levels: Vec<RgbaImage>,
+    regions: HashMap<String, AtlasRegion>,
+    /// Frame count, seconds per frame and distance in v between frames of each animated texture
+    animations: Vec<Vector3<f32>>,
+}
+
+impl TextureAtlas {
+    /// Pack `tiles` into rows on an image with power of two sides, tallest tiles first. A
+    /// placeholder tile is added unless one is given. Animated tiles past `MAX_ANIMATIONS` are
+    /// stuck on their first frame.
+    ///
+    /// Every frame is surrounded by copies of its edge texels and shrunk on its own for each mip
+    /// level, so filtering never picks up a neighbouring tile. There are as many levels as the
+    /// smallest frame can be halved, up to `MAX_MIP_LEVELS`.
+    pub fn stitch(mut tiles: Vec<(String, AtlasTile)>) -> Self {
+        if !tiles.iter().any(|(name, _)| name == PLACEHOLDER_TEXTURE) {
+            tiles.push((PLACEHOLDER_TEXTURE.to_string(), AtlasTile::still(placeholder_tile(16))));
+        }
+        tiles.sort_by(|(a_name, a), (b_name, b)| {
+            b.image.height().cmp(&a.image.height()).then(a_name.cmp(b_name))
+        });
+
+        let mip_levels = tiles
+            .iter()
+            .map(|(_, tile)| 1 + tile.image.width().trailing_zeros().min(tile.frame_height().trailing_zeros()))
+            .min()
+            .unwrap()
+            .min(MAX_MIP_LEVELS);
+        // Halved along with the frames, leaving at least a texel of padding on the smallest level
+        let padding = 1 << (mip_levels - 1);
+
+        let cell_sizes: Vec<(u32, u32)> = tiles
+            .iter()
+            .map(|(_, tile)| {
+                (
+                    tile.image.width() + 2 * padding,
+                    tile.frames * (tile.frame_height() + 2 * padding),
+                )
+            })
+            .collect();
+        let area: u32 = cell_sizes.iter().map(|(width, height)| width * height).sum();
+        let widest = cell_sizes.iter().map(|(width, _)| *width).max().unwrap();
+        let mut width = ((area as f32).sqrt().ceil() as u32).max(widest).next_power_of_two();
+        let (positions, used_height) = loop {
+            let (positions, used_height) = shelf_pack(&cell_sizes, width);
+            // Prefer a square atlas, widening it until the rows fit
+            if used_height <= width {
+                break (positions, used_height);
+            }
+            width *= 2;
+        };
+        let height = used_height.next_power_of_two();
+
+        let mut levels: Vec<RgbaImage> = (0..mip_levels)
+            .map(|level| RgbaImage::new(width >> level, height >> level))
+            .collect();
+        let mut regions = HashMap::new();
+        let mut animations = Vec::new();
+        for ((name, tile), (x, y)) in tiles.iter().zip(positions) {
+            // Frames stay stacked, so each is one padded frame height further down the atlas
+            let frame_height = tile.frame_height();
+            let frame_step = frame_height + 2 * padding;
+            for frame in 0..tile.frames {
+                let mut frame_image =
+                    imageops::crop_imm(&tile.image, 0, frame * frame_height, tile.image.width(), frame_height)
+                        .to_image();
+                for (level, level_image) in levels.iter_mut().enumerate() {
+                    if level > 0 {
+                        frame_image = downsample(&frame_image);
+                    }
+                    blit_padded(
+                        level_image,
+                        &frame_image,
+                        x >> level,
+                        (y + frame * frame_step) >> level,
+                        padding >> level,
+                    );
+                }
+            }
+
+            let animation = if tile.frames > 1 && animations.len() < MAX_ANIMATIONS {
+                animations.push(Vector3::new(
+                    tile.frames as f32,
+                    tile.frame_time,
+                    frame_step as f32 / height as f32,
+                ));
+                animations.len() as i32
+            } else {
+                0
+            };
+            let (left, top) = (x + padding, y + padding);
+            regions.insert(
+                name.clone(),
+                AtlasRegion {
+                    uv: [
+                        left as f32 / width as f32,
+                        1.0 - (top + frame_height) as f32 / height as f32,
+                        (left + tile.image.width()) as f32 / width as f32,
+                        1.0 - top as f32 / height as f32,
+                    ],
+                    animation,
+                },
+            );
+        }
+
+        Self {
+            levels,
+            regions,
+            animations,
+        }
+    }
+
+    /// The full size image followed by each mip level, half the size of the one before
+    pub fn levels(&self) -> &[RgbaImage] {
+        &self.levels
+    }
+
+    /// Where the texture called `name` is, or the placeholder if there is none
+    pub fn region(&self, name: &str) -> AtlasRegion {
+        match self.regions.get(name) {
+            Some(region) => *region,
+            None => self.regions[PLACEHOLDER_TEXTURE],
+        }
+    }
+
+    /// Regions of a block's six face textures, in face order
+    pub fn face_regions(&self, names: &[&str; 6]) -> [AtlasRegion; 6] {
+        names.map(|name| self.region(name))
+    }
+
+    /// Table of animations for the shaders' `animations` uniform
+    pub fn animations(&self) -> Vec<Vector3<f32>> {
+        self.animations.clone()
+    }
+}
+
+/// Positions for cells of `sizes` placed left to right in rows `width` wide, and the total height
+/// of the rows
+fn shelf_pack(sizes: &[(u32, u32)], width: u32) -> (Vec<(u32, u32)>, u32) {
+    let mut positions = Vec::with_capacity(sizes.len());
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+    for &(cell_width, cell_height) in sizes {
+        if x + cell_width > width {
+            x = 0;
+            y += row_height;
+            row_height = 0;
+        }
+        positions.push((x, y));
+        x += cell_width;
+        row_height = row_height.max(cell_height);
+    }
+    (positions, y + row_height)
+}
+
+/// Copy `frame` into `target` with its top left corner `padding` texels in from `(x, y)`, filling
+/// the padding with the frame's nearest edge texels
+fn blit_padded(target: &mut RgbaImage, frame: &RgbaImage, x: u32, y: u32, padding: u32) {
+    let (width, height) = frame.dimensions();
+    for dy in 0..height + 2 * padding {
+        for dx in 0..width + 2 * padding {
+            let source_x = dx.saturating_sub(padding).min(width - 1);
+            let source_y = dy.saturating_sub(padding).min(height - 1);
+            target.put_pixel(x + dx, y + dy, *frame.get_pixel(source_x, source_y));
+        }
+    }
+}
+
+/// Halve `image` in both directions, weighting colours by alpha so transparent texels don't darken
+/// the edges of leaves and plants
+fn downsample(image: &RgbaImage) -> RgbaImage {
+    RgbaImage::from_fn((image.width() / 2).max(1), (image.height() / 2).max(1), |x, y| {
+        let mut color = [0.0f32; 3];
+        let mut alpha = 0.0;
+        let mut samples = 0.0;
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            let sample_x = (2 * x + dx).min(image.width() - 1);
+            let sample_y = (2 * y + dy).min(image.height() - 1);
+            let texel = image.get_pixel(sample_x, sample_y);
+            let weight = texel[3] as f32;
+            for channel in 0..3 {
+                color[channel] += texel[channel] as f32 * weight;
+            }
+            alpha += weight;
+            samples += 1.0;
+        }
+        if alpha == 0.0 {
+            return Rgba([0, 0, 0, 0]);
+        }
+        Rgba([
+            (color[0] / alpha).round() as u8,
+            (color[1] / alpha).round() as u8,
+            (color[2] / alpha).round() as u8,
+            (alpha / samples).round() as u8,
+        ])
+    })
+}
+
+/// Magenta and black checkerboard which stands out wherever a texture is missing
+pub(crate) fn placeholder_tile(size: u32) -> RgbaImage {
+    let half = (size / 2).max(1);
+    RgbaImage::from_fn(size, size, |x, y| {
+        if (x / half + y / half).is_multiple_of(2) {
+            Rgba([255, 0, 255, 255])
+        } else {
+            Rgba([0, 0, 0, 255])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba(color))
+    }
+
+    /// Frames of distinct colours stacked into a strip
+    fn strip(size: u32, colors: &[[u8; 4]]) -> RgbaImage {
+        RgbaImage::from_fn(size, size * colors.len() as u32, |_, y| Rgba(colors[(y / size) as usize]))
+    }
+
+    #[test]
+    fn tiles_do_not_bleed_at_any_mip_level() {
+        let water = [[0, 0, 255, 255], [0, 64, 255, 255], [0, 128, 255, 255]];
+        let tiles = vec![
+            ("red".to_string(), AtlasTile::still(solid(16, 16, [255, 0, 0, 255]))),
+            ("green".to_string(), AtlasTile::still(solid(16, 16, [0, 255, 0, 255]))),
+            ("large".to_string(), AtlasTile::still(solid(32, 32, [255, 255, 0, 255]))),
+            ("wide".to_string(), AtlasTile::still(solid(32, 16, [0, 255, 255, 255]))),
+            ("clear".to_string(), AtlasTile::still(solid(16, 16, [0, 0, 0, 0]))),
+            ("water".to_string(), AtlasTile::animated(strip(16, &water), 3, 0.1).unwrap()),
+        ];
+        let expected: Vec<(&str, Vec<[u8; 4]>)> = vec![
+            ("red", vec![[255, 0, 0, 255]]),
+            ("green", vec![[0, 255, 0, 255]]),
+            ("large", vec![[255, 255, 0, 255]]),
+            ("wide", vec![[0, 255, 255, 255]]),
+            ("clear", vec![[0, 0, 0, 0]]),
+            ("water", water.to_vec()),
+        ];
+
+        let atlas = TextureAtlas::stitch(tiles);
+        assert_eq!(atlas.levels().len(), MAX_MIP_LEVELS as usize);
+
+        for (level, image) in atlas.levels().iter().enumerate() {
+            let (width, height) = image.dimensions();
+            assert_eq!(width, atlas.levels()[0].width() >> level);
+            assert_eq!(height, atlas.levels()[0].height() >> level);
+
+            for (name, colors) in &expected {
+                let region = atlas.region(name);
+                for (frame, color) in colors.iter().enumerate() {
+                    let frame_step = if region.animation > 0 {
+                        atlas.animations[region.animation as usize - 1].z
+                    } else {
+                        0.0
+                    };
+                    // Texel edges of the frame on this level, rows counted from the top of the image
+                    let left = region.uv[0] * width as f32;
+                    let right = region.uv[2] * width as f32;
+                    let top = (1.0 - region.uv[3] + frame as f32 * frame_step) * height as f32;
+                    let bottom = (1.0 - region.uv[1] + frame as f32 * frame_step) * height as f32;
+                    for edge in [left, right, top, bottom] {
+                        assert_eq!(edge.fract(), 0.0, "{} isn't texel aligned on level {}", name, level);
+                    }
+
+                    // Linear filtering inside the frame reads up to one texel past its edges
+                    for y in top as u32 - 1..bottom as u32 + 1 {
+                        for x in left as u32 - 1..right as u32 + 1 {
+                            assert_eq!(
+                                image.get_pixel(x, y).0,
+                                *color,
+                                "{} frame {} bleeds at ({}, {}) on level {}",
+                                name,
+                                frame,
+                                x,
+                                y,
+                                level
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn odd_sized_tiles_limit_mip_levels() {
+        let tiles = vec![
+            ("even".to_string(), AtlasTile::still(solid(16, 16, [255, 0, 0, 255]))),
+            ("odd".to_string(), AtlasTile::still(solid(12, 12, [0, 255, 0, 255]))),
+        ];
+        let atlas = TextureAtlas::stitch(tiles);
+        assert_eq!(atlas.levels().len(), 3);
+    }
+
+    #[test]
+    fn missing_textures_use_placeholder() {
+        let tiles = vec![("red".to_string(), AtlasTile::still(solid(16, 16, [255, 0, 0, 255])))];
+        let atlas = TextureAtlas::stitch(tiles);
+        assert_eq!(atlas.region("unknown").uv, atlas.region(PLACEHOLDER_TEXTURE).uv);
+    }
+}
@@ -58,17 +58,39 @@ impl Framebuffer {
             }
         }
 
-        let fb_status = unsafe { gl::CheckFramebufferStatus(gl::FRAMEBUFFER) };
-        if fb_status != gl::FRAMEBUFFER_COMPLETE {
-            #[cfg(target_feature = "android-lib")]
-            {
-                debug!(
-                    "Could not setup framebuffer: glCheckFramebufferStatus() returned {}",
-                    fb_status
-                );
-            }
-            panic!("Could not setup framebuffer! (error: {})", fb_status);
+        check_status();
+        framebuffer.unbind();
+        framebuffer
+    }
+
+    /// A framebuffer which only records depth into `depth_texture`, for shadow maps. The texture
+    /// is bound under the name `depth`.
+    pub fn with_depth_texture(depth_texture: Texture) -> Self {
+        let mut id = 0;
+        unsafe {
+            gl::GenFramebuffers(1, &mut id);
         }
+        let mut framebuffer = Self {
+            id,
+            textures: HashMap::new(),
+        };
+
+        framebuffer.bind();
+        unsafe {
+            gl::FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_ATTACHMENT,
+                gl::TEXTURE_2D,
+                depth_texture.id,
+                0,
+            );
+            let no_color = gl::NONE;
+            gl::DrawBuffers(1, &no_color);
+            gl::ReadBuffer(gl::NONE);
+        }
+        framebuffer.textures.insert("depth", depth_texture);
+
+        check_status();
 
         framebuffer.unbind();
         framebuffer
@@ -134,3 +156,18 @@ impl Framebuffer {
         }
     }
 }
+
+/// Panic if the bound framebuffer can't be rendered to
+fn check_status() {
+    let fb_status = unsafe { gl::CheckFramebufferStatus(gl::FRAMEBUFFER) };
+    if fb_status != gl::FRAMEBUFFER_COMPLETE {
+        #[cfg(target_feature = "android-lib")]
+        {
+            debug!(
+                "Could not setup framebuffer: glCheckFramebufferStatus() returned {}",
+                fb_status
+            );
+        }
+        panic!("Could not setup framebuffer! (error: {})", fb_status);
+    }
+}
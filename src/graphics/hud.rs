@@ -0,0 +1,373 @@
+use std::time::{Duration, Instant};
+
+use cgmath::{Matrix3, Matrix4, SquareMatrix, Vector3};
+use image::DynamicImage;
+
+use crate::{
+    c_str,
+    item::ItemKind,
+    player::{inventory::Inventory, mining::CRACK_STAGES},
+    terrain::{block::BLOCKS, block_state::Facing, BlockWorldPos, ChunkIndex, Terrain},
+};
+
+use super::{
+    atlas::AtlasRegion,
+    font::{font_atlas, push_text, text_width, LINE_HEIGHT},
+    mesh::{push_face, push_quad},
+    resources::GLResources,
+    shader::Shader,
+    source::{BLOCK_OVERLAY_FRAG_SRC, GUI_FRAG_SRC, GUI_VERT_SRC, TERRAIN_VERT_SRC},
+    texture::Texture,
+    texture_pack::TexturePack,
+    uniform::Uniform,
+    vertex::{Vertex2D, Vertex3D},
+};
+
+/// Length of each crosshair arm from the center of the screen, in pixels
+const CROSSHAIR_SIZE: f32 = 10.0;
+const CROSSHAIR_THICKNESS: f32 = 2.0;
+
+/// How far outlines and overlays sit outside the block, so they don't fight its faces for depth
+const OVERLAY_GROWTH: f32 = 0.002;
+
+/// Texture coordinates from 0 to 1 across each overlay face, which the crack pattern is laid out on
+const WHOLE_FACE: AtlasRegion = AtlasRegion {
+    uv: [0.0, 0.0, 1.0, 1.0],
+    animation: 0,
+};
+
+/// Screen height in pixels which the HUD is laid out for at a scale of 1. Taller screens scale it
+/// up in whole steps so the font stays crisp.
+const HUD_REFERENCE_HEIGHT: i32 = 360;
+
+/// Size of a hotbar slot before scaling, in pixels
+const SLOT_SIZE: f32 = 20.0;
+
+/// Weight given to the newest frame in the smoothed frame time
+const FRAME_TIME_SMOOTHING: f32 = 0.05;
+
+/// Numbers shown in the debug overlay, gathered while the engine's components are locked for drawing
+pub(crate) struct DebugInfo {
+    pub position: Vector3<f32>,
+    pub chunk_index: ChunkIndex,
+    pub biome: &'static str,
+    pub tick_time: Duration,
+    pub visible_chunks: usize,
+    pub loaded_chunks: usize,
+    pub pending_vao_updates: usize,
+}
+
+/// Everything drawn to show what the player is doing: the crosshair, hotbar and debug overlay over
+/// the finished frame, and the outline and cracks on the targeted block within the scene
+pub(crate) struct Hud {
+    pub(crate) debug_overlay: bool,
+    last_frame: Option<Instant>,
+    /// Seconds between frames, smoothed so the overlay is readable
+    frame_time: f32,
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Self {
+            debug_overlay: false,
+            last_frame: None,
+            frame_time: 0.0,
+        }
+    }
+
+    pub fn init_gl_resources(&self, gl_resources: &mut GLResources) {
+        let gui_program = Shader::new(GUI_VERT_SRC, GUI_FRAG_SRC).unwrap();
+        gl_resources.add_shader("gui", gui_program);
+        let overlay_program = Shader::new(TERRAIN_VERT_SRC, BLOCK_OVERLAY_FRAG_SRC).unwrap();
+        gl_resources.add_shader("block_overlay", overlay_program);
+
+        gl_resources.add_vao("crosshair".to_string(), Box::new(crosshair_vertices()));
+        gl_resources.add_vao("box_edges".to_string(), Box::new(box_edge_vertices()));
+
+        let mut box_faces = Vec::new();
+        for face in 0..6 {
+            push_face(&[0.0, 0.0, 0.0], face, &mut box_faces, &WHOLE_FACE, 0, 0.0);
+        }
+        gl_resources.add_vao("box_faces".to_string(), Box::new(box_faces));
+
+        gl_resources.add_texture("font", Texture::from_dynamic_image(DynamicImage::ImageRgba8(font_atlas())));
+    }
+
+    /// Rebuild the hotbar and debug overlay meshes for this frame. The debug overlay is only shown
+    /// when `debug_info` is given.
+    pub fn update(
+        &mut self,
+        gl_resources: &mut GLResources,
+        inventory: &Inventory,
+        texture_pack: &TexturePack,
+        debug_info: Option<DebugInfo>,
+        width: i32,
+        height: i32,
+    ) {
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame {
+            let delta_time = (now - last_frame).as_secs_f32();
+            self.frame_time = if self.frame_time > 0.0 {
+                self.frame_time + (delta_time - self.frame_time) * FRAME_TIME_SMOOTHING
+            } else {
+                delta_time
+            };
+        }
+        self.last_frame = Some(now);
+
+        let scale = hud_scale(height);
+        let mut panels = Vec::new();
+        let mut highlights = Vec::new();
+        let mut icons = Vec::new();
+        let mut text = Vec::new();
+
+        /* Hotbar along the bottom of the screen */
+        let slot_size = SLOT_SIZE * scale;
+        let slots = inventory.slots();
+        let left = -slot_size * slots.len() as f32 / 2.0;
+        let bottom = -(height as f32) / 2.0 + 2.0 * scale;
+        for (i, slot) in slots.iter().enumerate() {
+            let x = left + i as f32 * slot_size;
+            if i == inventory.selected_slot() {
+                push_quad([x, bottom, x + slot_size, bottom + slot_size], [0.0; 4], &mut highlights);
+            }
+            push_quad(
+                [x + scale, bottom + scale, x + slot_size - scale, bottom + slot_size - scale],
+                [0.0; 4],
+                &mut panels,
+            );
+
+            let stack = match slot {
+                Some(stack) => stack,
+                None => continue,
+            };
+            let item = stack.item();
+            let icon = match item.kind {
+                ItemKind::Block(block_id) => BLOCKS[block_id].texture_map.map(|texture| texture.icon()),
+                _ => None,
+            };
+            match icon {
+                Some(texture_name) => {
+                    let inset = 3.0 * scale;
+                    push_quad(
+                        [x + inset, bottom + inset, x + slot_size - inset, bottom + slot_size - inset],
+                        texture_pack.atlas.region(texture_name).uv,
+                        &mut icons,
+                    );
+                }
+                // Items without a texture are labelled with the start of their name
+                None => {
+                    let label: String = item.name.chars().take(2).collect();
+                    let label_x = x + (slot_size - text_width(&label, scale)) / 2.0;
+                    push_text(&label, label_x, bottom + slot_size - 4.0 * scale, scale, &mut text);
+                }
+            }
+            if stack.count > 1 {
+                let count = stack.count.to_string();
+                let count_x = x + slot_size - 2.0 * scale - text_width(&count, scale);
+                push_text(&count, count_x, bottom + (LINE_HEIGHT as f32 + 1.0) * scale, scale, &mut text);
+            }
+        }
+
+        /* Debug overlay down the top left corner */
+        if let Some(info) = debug_info {
+            let lines = [
+                format!(
+                    "Position: {:.1} {:.1} {:.1}",
+                    info.position.x, info.position.y, info.position.z
+                ),
+                format!("Chunk: {} {}", info.chunk_index.x, info.chunk_index.y),
+                format!("Biome: {}", info.biome),
+                format!(
+                    "FPS: {:.0} ({:.1} ms)",
+                    if self.frame_time > 0.0 { 1.0 / self.frame_time } else { 0.0 },
+                    self.frame_time * 1000.0
+                ),
+                format!("Tick: {:.1} ms", info.tick_time.as_secs_f32() * 1000.0),
+                format!("Chunks: {} visible, {} loaded", info.visible_chunks, info.loaded_chunks),
+                format!("VAO updates queued: {}", info.pending_vao_updates),
+            ];
+            let line_height = LINE_HEIGHT as f32 * scale;
+            let x = -(width as f32) / 2.0 + 2.0 * scale;
+            let top = height as f32 / 2.0 - 2.0 * scale;
+            for (i, line) in lines.iter().enumerate() {
+                let y = top - i as f32 * line_height;
+                push_quad(
+                    [x - scale, y - line_height, x + text_width(line, scale) + scale, y],
+                    [0.0; 4],
+                    &mut panels,
+                );
+                push_text(line, x, y - scale, scale, &mut text);
+            }
+        }
+
+        gl_resources.create_or_update_vao("hud_panels".to_string(), Box::new(panels));
+        gl_resources.create_or_update_vao("hud_highlights".to_string(), Box::new(highlights));
+        gl_resources.create_or_update_vao("hud_icons".to_string(), Box::new(icons));
+        gl_resources.create_or_update_vao("hud_text".to_string(), Box::new(text));
+    }
+
+    /// Outline the targeted block, highlight the face being looked at and crack the block being
+    /// broken. Drawn into the scene so the depth already there hides what's behind terrain.
+    pub fn draw_block_overlays(
+        &self,
+        gl_resources: &GLResources,
+        terrain: &Terrain,
+        uniforms: &[(&str, Box<dyn Uniform>)],
+        target: Option<(BlockWorldPos, BlockWorldPos)>,
+        breaking: Option<(BlockWorldPos, usize)>,
+    ) {
+        let shader = gl_resources.get_shader("block_overlay").unwrap();
+        let box_edges = gl_resources.get_vao("box_edges").unwrap();
+        let box_faces = gl_resources.get_vao("box_faces").unwrap();
+
+        shader.use_program();
+        for (name, uniform) in uniforms {
+            uniform.set_as_uniform(shader, name);
+        }
+
+        unsafe {
+            gl::DepthMask(gl::FALSE);
+            gl::Enable(gl::POLYGON_OFFSET_FILL);
+            gl::PolygonOffset(-1.0, -1.0);
+        }
+
+        if let Some((world_pos, stage)) = breaking {
+            Vector3::new(0.0, 0.0, 0.0).set_as_uniform(shader, "overlay_color");
+            0.6.set_as_uniform(shader, "overlay_alpha");
+            ((stage + 1) as f32 / CRACK_STAGES as f32).set_as_uniform(shader, "crack_progress");
+            for model_matrix in overlay_matrices(terrain, &world_pos) {
+                model_matrix.set_as_uniform(shader, "model_matrix");
+                box_faces.draw();
+            }
+        }
+
+        if let Some((world_pos, hit_normal)) = target {
+            (-1.0).set_as_uniform(shader, "crack_progress");
+            let hit_face = Facing::from_normal(&hit_normal).map(|facing| facing.face_index());
+            for model_matrix in overlay_matrices(terrain, &world_pos) {
+                model_matrix.set_as_uniform(shader, "model_matrix");
+
+                if let Some(face) = hit_face {
+                    Vector3::new(1.0, 1.0, 1.0).set_as_uniform(shader, "overlay_color");
+                    0.15.set_as_uniform(shader, "overlay_alpha");
+                    box_faces.draw_range(face * 6, 6);
+                }
+
+                Vector3::new(0.0, 0.0, 0.0).set_as_uniform(shader, "overlay_color");
+                0.5.set_as_uniform(shader, "overlay_alpha");
+                box_edges.draw_lines();
+            }
+        }
+
+        unsafe {
+            gl::Disable(gl::POLYGON_OFFSET_FILL);
+            gl::DepthMask(gl::TRUE);
+        }
+    }
+
+    /// Draw the crosshair, hotbar and debug overlay over the finished frame
+    pub fn draw(&self, gl_resources: &GLResources, width: i32, height: i32) {
+        let shader = gl_resources.get_shader("gui").unwrap();
+
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Disable(gl::CULL_FACE);
+        }
+
+        shader.use_program();
+        pixel_matrix(width, height).set_as_uniform(shader, "model_matrix");
+        shader.set_texture(unsafe { c_str!("texture_map") }, 0);
+
+        let layers = [
+            ("hud_highlights", None, Vector3::new(1.0, 1.0, 1.0), 0.6),
+            ("hud_panels", None, Vector3::new(0.0, 0.0, 0.0), 0.45),
+            ("hud_icons", Some("terrain"), Vector3::new(1.0, 1.0, 1.0), 1.0),
+            ("hud_text", Some("font"), Vector3::new(1.0, 1.0, 1.0), 1.0),
+        ];
+        for (vao_name, texture_name, color, alpha) in layers {
+            let vao = match gl_resources.get_vao(vao_name) {
+                Some(vao) => vao,
+                None => continue,
+            };
+            match texture_name.and_then(|name| gl_resources.get_texture(name)) {
+                Some(texture) => {
+                    texture.use_as_framebuffer_texture(0);
+                    1.0.set_as_uniform(shader, "textured");
+                }
+                None => 0.0.set_as_uniform(shader, "textured"),
+            }
+            color.set_as_uniform(shader, "color");
+            alpha.set_as_uniform(shader, "alpha");
+            vao.draw();
+        }
+
+        // The crosshair inverts what's behind it so it shows up on any background
+        unsafe {
+            gl::BlendFunc(gl::ONE_MINUS_DST_COLOR, gl::ZERO);
+        }
+        0.0.set_as_uniform(shader, "textured");
+        Vector3::new(1.0, 1.0, 1.0).set_as_uniform(shader, "color");
+        1.0.set_as_uniform(shader, "alpha");
+        gl_resources.get_vao("crosshair").unwrap().draw();
+
+        unsafe {
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            gl::Enable(gl::CULL_FACE);
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+}
+
+/// Whole number scale for the HUD on a screen `height` pixels tall
+fn hud_scale(height: i32) -> f32 {
+    (height / HUD_REFERENCE_HEIGHT).max(1) as f32
+}
+
+/// Maps positions in pixels from the center of the screen to normalized device coordinates
+fn pixel_matrix(width: i32, height: i32) -> Matrix3<f32> {
+    Matrix3::from_diagonal(Vector3::new(2.0 / width as f32, 2.0 / height as f32, 1.0))
+}
+
+/// Unit cube transforms covering each selection box of the block at `world_pos`
+fn overlay_matrices(terrain: &Terrain, world_pos: &BlockWorldPos) -> Vec<Matrix4<f32>> {
+    terrain
+        .selection_boxes_at_world_pos(world_pos)
+        .iter()
+        .map(|rect| {
+            let growth = Vector3::new(OVERLAY_GROWTH, OVERLAY_GROWTH, OVERLAY_GROWTH);
+            let size = rect.size + growth * 2.0;
+            Matrix4::from_translation(rect.pos - growth) * Matrix4::from_nonuniform_scale(size.x, size.y, size.z)
+        })
+        .collect()
+}
+
+/// A horizontal and a vertical bar crossing at the origin, with the vertical one split so the
+/// center isn't inverted twice
+fn crosshair_vertices() -> Vec<Vertex2D> {
+    let half = CROSSHAIR_THICKNESS / 2.0;
+    let mut vertices = Vec::new();
+    push_quad([-CROSSHAIR_SIZE, -half, CROSSHAIR_SIZE, half], [0.0; 4], &mut vertices);
+    push_quad([-half, half, half, CROSSHAIR_SIZE], [0.0; 4], &mut vertices);
+    push_quad([-half, -CROSSHAIR_SIZE, half, -half], [0.0; 4], &mut vertices);
+    vertices
+}
+
+/// The twelve edges of a unit cube as pairs of line end points
+fn box_edge_vertices() -> Vec<Vertex3D> {
+    let corner = |i: usize| Vector3::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32);
+    let mut vertices = Vec::new();
+    for i in 0..8 {
+        for axis_bit in [1, 2, 4] {
+            if i & axis_bit == 0 {
+                for end in [i, i | axis_bit] {
+                    vertices.push(Vertex3D {
+                        position: corner(end),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+    vertices
+}
@@ -1,8 +1,16 @@
 use cgmath::{Vector2, Vector3};
 
-use crate::terrain::block::{self, Block, MeshType};
+use crate::terrain::{
+    block::{Block, MeshType},
+    block_state::BlockState,
+    model::{block_model, ModelBox},
+};
 
-use super::vertex::Vertex3D;
+use super::{
+    atlas::AtlasRegion,
+    texture_pack::TexturePack,
+    vertex::{Vertex2D, Vertex3D},
+};
 
 const CUBE_FACES: [[Vertex3D; 6]; 10] = [
     // Facing positive-X
@@ -13,6 +21,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-right
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 0.0),
@@ -20,6 +29,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 1.0),
@@ -27,6 +37,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-right
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 1.0),
@@ -34,6 +45,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-right
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 0.0),
@@ -41,6 +53,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 0.0),
@@ -48,6 +61,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-right
     ],
     // Facing negative-X
@@ -58,6 +72,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-left
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 0.0),
@@ -65,6 +80,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-left
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -72,6 +88,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -79,6 +96,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 0.0),
@@ -86,6 +104,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-left
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 0.0),
@@ -93,6 +112,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-left
     ],
     // Facing positive-Y
@@ -103,6 +123,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-right
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 0.0),
@@ -110,6 +131,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-right
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 1.0),
@@ -117,6 +139,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-left
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 1.0),
@@ -124,6 +147,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-left
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 0.0),
@@ -131,6 +155,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-right
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 0.0),
@@ -138,6 +163,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-left
     ],
     // Facing negative-Y
@@ -148,6 +174,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-right
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -155,6 +182,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 0.0),
@@ -162,6 +190,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -169,6 +198,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 0.0),
@@ -176,6 +206,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-left
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 0.0),
@@ -183,6 +214,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
     ],
     // Facing positive-Z
@@ -193,6 +225,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-right
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 1.0),
@@ -200,6 +233,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-left
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -207,6 +241,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 1.0),
@@ -214,6 +249,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-top-right
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 1.0),
@@ -221,6 +257,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-left
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 1.0),
@@ -228,6 +265,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Front-bottom-right
     ],
     // Facing negative-Z
@@ -238,6 +276,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
         Vertex3D {
             position: Vector3::new(0.0, 0.0, 0.0),
@@ -245,6 +284,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-left
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 0.0),
@@ -252,6 +292,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-left
         Vertex3D {
             position: Vector3::new(1.0, 0.0, 0.0),
@@ -259,6 +300,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-bottom-right
         Vertex3D {
             position: Vector3::new(0.0, 1.0, 0.0),
@@ -266,6 +308,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-left
         Vertex3D {
             position: Vector3::new(1.0, 1.0, 0.0),
@@ -273,6 +316,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }, // Back-top-right
     ],
     // Diagonal (0, 0) -> (1, 1)
@@ -283,6 +327,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.853_553_4),
@@ -290,6 +335,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.0, 0.146_446_62),
@@ -297,6 +343,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.99, 0.146_446_62),
@@ -304,6 +351,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.99, 0.853_553_4),
@@ -311,6 +359,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.853_553_4),
@@ -318,6 +367,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
     ],
     // Diagonal (1, 1) -> (0, 0)
@@ -328,6 +378,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.0, 0.146_446_62),
@@ -335,6 +386,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.853_553_4),
@@ -342,6 +394,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.99, 0.146_446_62),
@@ -349,6 +402,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.853_553_4),
@@ -356,6 +410,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.99, 0.853_553_4),
@@ -363,6 +418,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
     ],
     // Diagonal (0, 1) -> (1, 0)
@@ -373,6 +429,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.146_446_62),
@@ -380,6 +437,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.0, 0.853_553_4),
@@ -387,6 +445,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.99, 0.853_553_4),
@@ -394,6 +453,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.99, 0.146_446_62),
@@ -401,6 +461,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.146_446_62),
@@ -408,6 +469,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
     ],
     // Diagonal (1, 0) -> (0, 1)
@@ -418,6 +480,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.0, 0.853_553_4),
@@ -425,6 +488,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.146_446_62),
@@ -432,6 +496,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.146_446_62, 0.99, 0.853_553_4),
@@ -439,6 +504,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(0.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.0, 0.146_446_62),
@@ -446,6 +512,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 0.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
         Vertex3D {
             position: Vector3::new(0.853_553_4, 0.99, 0.146_446_62),
@@ -453,6 +520,7 @@ const CUBE_FACES: [[Vertex3D; 6]; 10] = [
             tex_coords: Vector2::new(1.0, 1.0),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         },
     ],
 ];
@@ -465,6 +533,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 0.0),
@@ -472,6 +541,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 1.0),
@@ -479,6 +549,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 1.0),
@@ -486,6 +557,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 0.0),
@@ -493,6 +565,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 0.0),
@@ -500,6 +573,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     // Facing negative-X
     Vertex3D {
@@ -508,6 +582,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 0.0),
@@ -515,6 +590,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -522,6 +598,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -529,6 +606,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 0.0),
@@ -536,6 +614,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 0.0),
@@ -543,6 +622,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     // Facing positive-Y
     Vertex3D {
@@ -551,6 +631,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 0.0),
@@ -558,6 +639,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 1.0),
@@ -565,6 +647,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 1.0),
@@ -572,6 +655,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 0.0),
@@ -579,6 +663,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 0.0),
@@ -586,6 +671,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     // Facing negative-Y
     Vertex3D {
@@ -594,6 +680,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -601,6 +688,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 0.0),
@@ -608,6 +696,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -615,6 +704,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 0.0),
@@ -622,6 +712,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 0.0),
@@ -629,6 +720,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     // Facing positive-Z
     Vertex3D {
@@ -637,6 +729,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 1.0),
@@ -644,6 +737,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -651,6 +745,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 1.0),
@@ -658,6 +753,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 1.0),
@@ -665,6 +761,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 1.0),
@@ -672,6 +769,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     // Facing negative-Z
     Vertex3D {
@@ -680,6 +778,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(0.0, 0.0, 0.0),
@@ -687,6 +786,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 0.0),
@@ -694,6 +794,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(1.0, 0.0, 0.0),
@@ -701,6 +802,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(0.0, 1.0, 0.0),
@@ -708,6 +810,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 0.0),
@@ -715,6 +818,7 @@ pub(crate) const _DEFAULT_CUBE: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
 ];
 
@@ -725,6 +829,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-1.0, -1.0, 0.0),
@@ -732,6 +837,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     Vertex3D {
         position: Vector3::new(-1.0, 1.0, 0.0),
@@ -739,6 +845,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(1.0, -1.0, 0.0),
@@ -746,6 +853,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-1.0, 1.0, 0.0),
@@ -753,6 +861,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(1.0, 1.0, 0.0),
@@ -760,6 +869,7 @@ pub(crate) const FULLSCREEN_QUAD: [Vertex3D; 6] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
 ];
 
@@ -767,18 +877,70 @@ pub(crate) fn push_face(
     position: &[f32; 3],
     face: usize,
     vertices: &mut Vec<Vertex3D>,
-    texmap_offset: &(f32, f32),
+    tile: &AtlasRegion,
     vertex_type: i32,
     lighting: f32,
 ) {
+    push_face_rotated(position, face, vertices, tile, 0, vertex_type, lighting);
+}
+
+/// Same as `push_face`, with the face's texture rotated clockwise by `uv_rotation` quarter turns
+pub(crate) fn push_face_rotated(
+    position: &[f32; 3],
+    face: usize,
+    vertices: &mut Vec<Vertex3D>,
+    tile: &AtlasRegion,
+    uv_rotation: usize,
+    vertex_type: i32,
+    lighting: f32,
+) {
+    push_box_face(
+        position,
+        face,
+        &ModelBox::FULL,
+        &[0.0, 0.0, 1.0, 1.0],
+        vertices,
+        tile,
+        uv_rotation,
+        vertex_type,
+        lighting,
+    );
+}
+
+/// Push `face` of a box within the block, textured with the `uv` area (u0, v0, u1, v1) of `tile`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn push_box_face(
+    position: &[f32; 3],
+    face: usize,
+    model_box: &ModelBox,
+    uv: &[f32; 4],
+    vertices: &mut Vec<Vertex3D>,
+    tile: &AtlasRegion,
+    uv_rotation: usize,
+    vertex_type: i32,
+    lighting: f32,
+) {
+    let size = model_box.max - model_box.min;
     for v in 0..6 {
         let mut vertex = CUBE_FACES[face][v];
+        vertex.position = model_box.min
+            + Vector3::new(
+                vertex.position.x * size.x,
+                vertex.position.y * size.y,
+                vertex.position.z * size.z,
+            );
         vertex.position.x += position[0];
         vertex.position.y += position[1];
         vertex.position.z += position[2];
 
-        vertex.tex_coords.x = vertex.tex_coords.x * 0.0625 + 0.0625 * texmap_offset.0;
-        vertex.tex_coords.y = vertex.tex_coords.y * 0.0625 + 0.0625 * texmap_offset.1;
+        for _ in 0..uv_rotation % 4 {
+            vertex.tex_coords = Vector2::new(vertex.tex_coords.y, 1.0 - vertex.tex_coords.x);
+        }
+        let tile_u = uv[0] + (uv[2] - uv[0]) * vertex.tex_coords.x;
+        let tile_v = uv[1] + (uv[3] - uv[1]) * vertex.tex_coords.y;
+        vertex.tex_coords.x = tile.uv[0] + (tile.uv[2] - tile.uv[0]) * tile_u;
+        vertex.tex_coords.y = tile.uv[1] + (tile.uv[3] - tile.uv[1]) * tile_v;
+        vertex.animation = tile.animation;
 
         vertex.vtype = vertex_type;
         vertex.lighting = lighting;
@@ -787,57 +949,15 @@ pub(crate) fn push_face(
     }
 }
 
-pub(crate) fn block_drop_vertices(block: &Block) -> Vec<Vertex3D> {
+pub(crate) fn block_drop_vertices(block: &Block, texture_pack: &TexturePack) -> Vec<Vertex3D> {
     let mut vertices = Vec::new();
     let mesh_type = block.mesh_type;
     let vertex_type = block.block_type as i32;
     let texture_map = block.texture_map;
     let dummy_position = [0.0, 0.0, 0.0];
-    let tex_coords: [(f32, f32); 6] = if let Some(texture_type) = &texture_map {
-        let mut coords = [(0.0f32, 0.0f32); 6];
-        match texture_type {
-            block::TextureType::Single(x, y) => {
-                for item in &mut coords {
-                    *item = (*x, *y);
-                }
-            }
-            block::TextureType::TopAndSide((x_top, y_top), (x_side, y_side)) => {
-                coords[0] = (*x_side, *y_side);
-                coords[1] = (*x_side, *y_side);
-                coords[2] = (*x_top, *y_top);
-                coords[3] = (*x_side, *y_side);
-                coords[4] = (*x_side, *y_side);
-                coords[5] = (*x_side, *y_side);
-            }
-            block::TextureType::TopSideBottom(
-                (x_top, y_top),
-                (x_side, y_side),
-                (x_bottom, y_bottom),
-            ) => {
-                coords[0] = (*x_side, *y_side);
-                coords[1] = (*x_side, *y_side);
-                coords[2] = (*x_top, *y_top);
-                coords[3] = (*x_bottom, *y_bottom);
-                coords[4] = (*x_side, *y_side);
-                coords[5] = (*x_side, *y_side);
-            }
-            block::TextureType::TopSideFrontActivatable(
-                (x_front_inactive, y_front_inactive),
-                (_x_front_active, _y_front_active),
-                (x_side, y_side),
-                (x_top, y_top),
-            ) => {
-                coords[0] = (*x_side, *y_side);
-                coords[1] = (*x_side, *y_side);
-                coords[2] = (*x_top, *y_top);
-                coords[3] = (*x_top, *y_top);
-                coords[4] = (*x_side, *y_side);
-                coords[5] = (*x_front_inactive, *y_front_inactive);
-            }
-        }
-        coords
-    } else {
-        [(0.0, 0.0); 6]
+    let tex_coords = match &texture_map {
+        Some(texture_type) => texture_pack.atlas.face_regions(&texture_type.face_textures(false)),
+        None => [AtlasRegion::default(); 6],
     };
     match mesh_type {
         MeshType::Block => {
@@ -924,7 +1044,38 @@ pub(crate) fn block_drop_vertices(block: &Block) -> Vec<Vertex3D> {
                 1.0,
             );
         }
+        MeshType::Model(name) => {
+            for model_box in block_model(name).boxes(block, &BlockState::default()) {
+                for (face, face_coords) in tex_coords.iter().enumerate() {
+                    if let Some(uv) = model_box.face_uv(face) {
+                        push_box_face(
+                            &dummy_position,
+                            face,
+                            &model_box,
+                            &uv,
+                            &mut vertices,
+                            face_coords,
+                            0,
+                            vertex_type,
+                            1.0,
+                        );
+                    }
+                }
+            }
+        }
     }
 
     vertices
 }
+
+/// Push two triangles covering `rect` as `[left, bottom, right, top]`, textured with `uv` laid out
+/// the same way
+pub(crate) fn push_quad(rect: [f32; 4], uv: [f32; 4], vertices: &mut Vec<Vertex2D>) {
+    let corners = [(0, 1), (2, 1), (2, 3), (0, 1), (2, 3), (0, 3)];
+    for (x, y) in corners {
+        vertices.push(Vertex2D {
+            position: Vector2::new(rect[x], rect[y]),
+            tex_coords: Vector2::new(uv[x], uv[y]),
+        });
+    }
+}
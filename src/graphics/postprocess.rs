@@ -0,0 +1,156 @@
+use crate::c_str;
+
+use super::{
+    framebuffer::Framebuffer,
+    resources::GLResources,
+    shader::Shader,
+    source::{
+        POST_GAMMA_FRAG_SRC, POST_TONE_MAPPING_FRAG_SRC, POST_UNDERWATER_FRAG_SRC, POST_VIGNETTE_FRAG_SRC,
+        SCREENQUAD_VERT_SRC,
+    },
+    texture::{Texture, TextureFormat},
+    uniform::Uniform,
+    vao::VertexAttributeObject,
+};
+
+/// A full screen effect applied to the composited frame
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PostEffect {
+    ToneMapping,
+    Gamma,
+    Vignette,
+    /// Only drawn while the camera is inside a fluid
+    Underwater,
+}
+
+impl PostEffect {
+    const ALL: [PostEffect; 4] = [
+        PostEffect::ToneMapping,
+        PostEffect::Gamma,
+        PostEffect::Vignette,
+        PostEffect::Underwater,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PostEffect::ToneMapping => "tone_mapping",
+            PostEffect::Gamma => "gamma",
+            PostEffect::Vignette => "vignette",
+            PostEffect::Underwater => "underwater",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|effect| effect.name() == name)
+    }
+
+    /// Name the effect's shader is registered under in `GLResources`
+    fn shader_name(self) -> &'static str {
+        match self {
+            PostEffect::ToneMapping => "post_tone_mapping",
+            PostEffect::Gamma => "post_gamma",
+            PostEffect::Vignette => "post_vignette",
+            PostEffect::Underwater => "post_underwater",
+        }
+    }
+
+    fn fragment_source(self) -> &'static str {
+        match self {
+            PostEffect::ToneMapping => POST_TONE_MAPPING_FRAG_SRC,
+            PostEffect::Gamma => POST_GAMMA_FRAG_SRC,
+            PostEffect::Vignette => POST_VIGNETTE_FRAG_SRC,
+            PostEffect::Underwater => POST_UNDERWATER_FRAG_SRC,
+        }
+    }
+}
+
+/// Runs the configured effects in order, bouncing between two framebuffers and drawing the last
+/// one to the screen
+pub(crate) struct PostProcessChain {
+    effects: Vec<PostEffect>,
+}
+
+impl PostProcessChain {
+    pub fn new(effects: &[PostEffect]) -> Self {
+        Self {
+            effects: effects.to_vec(),
+        }
+    }
+
+    pub fn init_gl_resources(&self, gl_resources: &mut GLResources, width: i32, height: i32) {
+        for effect in &self.effects {
+            let program = Shader::new(SCREENQUAD_VERT_SRC, effect.fragment_source()).unwrap();
+            gl_resources.add_shader(effect.shader_name(), program);
+        }
+
+        // A chain of one effect reads the composite output and writes straight to the screen
+        if self.effects.len() > 1 {
+            for name in ["post_ping", "post_pong"] {
+                let color = Texture::empty(width, height, TextureFormat::Color);
+                gl_resources.add_framebuffer(name, Framebuffer::with_textures(&[("color", color)], None));
+            }
+        }
+    }
+
+    /// Apply the chain to the `color` texture of `input` and draw the result to the screen, copying
+    /// it unchanged when no effect is active
+    pub fn draw(
+        &self,
+        gl_resources: &GLResources,
+        screenquad: &VertexAttributeObject,
+        input: &Framebuffer,
+        uniforms: &[(&str, Box<dyn Uniform>)],
+        underwater: bool,
+    ) {
+        let passes: Vec<PostEffect> = self
+            .effects
+            .iter()
+            .copied()
+            .filter(|effect| underwater || *effect != PostEffect::Underwater)
+            .collect();
+
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+        }
+
+        if passes.is_empty() {
+            let passthrough = gl_resources.get_shader("postprocess").unwrap();
+            passthrough.use_program();
+            input.bind_render_textures_to_current_fb(&[("color", 0)]);
+            passthrough.set_texture(unsafe { c_str!("composite_output") }, 0);
+            screenquad.draw();
+        }
+
+        let mut source = input;
+        for (i, effect) in passes.iter().enumerate() {
+            let target = if i + 1 == passes.len() {
+                None
+            } else if i % 2 == 0 {
+                gl_resources.get_framebuffer("post_ping")
+            } else {
+                gl_resources.get_framebuffer("post_pong")
+            };
+            match target {
+                Some(target) => target.bind(),
+                None => source.unbind(),
+            }
+
+            let program = gl_resources.get_shader(effect.shader_name()).unwrap();
+            program.use_program();
+            for (name, uniform) in uniforms {
+                uniform.set_as_uniform(program, name);
+            }
+            source.bind_render_textures_to_current_fb(&[("color", 0)]);
+            program.set_texture(unsafe { c_str!("input_texture") }, 0);
+            screenquad.draw();
+
+            if let Some(target) = target {
+                source = target;
+            }
+        }
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+}
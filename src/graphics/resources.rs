@@ -1,7 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
 use super::{
-    framebuffer::Framebuffer, shader::Shader, texture::Texture, uniform::Uniform,
+    framebuffer::Framebuffer, shader::Shader, texture::Texture, texture_pack::TexturePack, uniform::Uniform,
     vao::VertexAttributeObject, vbo::VertexBufferObject, vertex::VertexBufferContents,
 };
 
@@ -11,7 +11,8 @@ pub struct GLResources {
     vaos: HashMap<String, VertexAttributeObject>,
     framebuffers: HashMap<&'static str, Framebuffer>,
 
-    vao_update_queue: VecDeque<(String, Box<dyn VertexBufferContents + Send + Sync>)>,
+    /// Buffers to upload, or to delete when the contents are `None`
+    vao_update_queue: VecDeque<(String, Option<Box<dyn VertexBufferContents + Send + Sync>>)>,
 }
 
 impl GLResources {
@@ -105,19 +106,44 @@ impl GLResources {
         name: String,
         buffer_contents: Box<dyn VertexBufferContents + Send + Sync>,
     ) {
-        self.vao_update_queue.push_front((name, buffer_contents));
+        // Only the newest contents matter, and an older upload left behind would overwrite them
+        self.vao_update_queue.retain(|(buffer_name, _)| *buffer_name != name);
+        self.vao_update_queue.push_front((name, Some(buffer_contents)));
+    }
+
+    pub fn remove_vao(&mut self, name: &str) {
+        if let Some(mut vao) = self.vaos.remove(name) {
+            vao.delete();
+        }
+    }
+
+    /// Queue the VAO `name` for deletion, dropping any update still waiting to be uploaded to it
+    pub fn remove_vao_buffer(&mut self, name: String) {
+        self.vao_update_queue.retain(|(buffer_name, _)| *buffer_name != name);
+        self.vao_update_queue.push_front((name, None));
+    }
+
+    /// Buffer uploads waiting to be processed at the start of a frame
+    pub fn pending_vao_updates(&self) -> usize {
+        self.vao_update_queue.len()
     }
 
     pub fn process_vao_buffer_updates(&mut self, num_per_frame: usize) {
         for _ in 0..num_per_frame {
-            if let Some((buffer_name, new_contents)) = self.vao_update_queue.pop_front() {
-                self.create_or_update_vao(buffer_name, new_contents);
+            match self.vao_update_queue.pop_front() {
+                Some((buffer_name, Some(new_contents))) => self.create_or_update_vao(buffer_name, new_contents),
+                Some((buffer_name, None)) => self.remove_vao(&buffer_name),
+                None => break,
             }
         }
     }
 }
 
 pub trait GLRenderable {
-    fn init_gl_resources(&self, gl_resources: &mut GLResources);
+    /// Create what's needed to draw, textured from `texture_pack`
+    fn init_gl_resources(&self, gl_resources: &mut GLResources, texture_pack: &TexturePack);
     fn draw(&self, gl_resources: &GLResources, uniforms: &[(&str, Box<dyn Uniform>)]);
+
+    /// Draw into the bound shadow map, anything that doesn't cast shadows can leave this out
+    fn draw_shadow(&self, _gl_resources: &GLResources, _uniforms: &[(&str, Box<dyn Uniform>)]) {}
 }
@@ -114,6 +114,19 @@ impl Shader {
         }
     }
 
+    pub fn set_vec3_array(&self, name: &CStr, vecs: &[Vector3<f32>]) {
+        if vecs.is_empty() {
+            return;
+        }
+        unsafe {
+            gl::Uniform3fv(
+                gl::GetUniformLocation(self.id, name.as_ptr()),
+                vecs.len() as i32,
+                vecs[0].as_ptr(),
+            );
+        }
+    }
+
     pub fn set_vec2(&self, name: &CStr, vec: &Vector2<f32>) {
         unsafe {
             gl::Uniform2fv(
@@ -0,0 +1,142 @@
+use cgmath::{ortho, InnerSpace, Matrix4, Point3, Vector3, Vector4};
+
+use super::{
+    framebuffer::Framebuffer,
+    resources::{GLRenderable, GLResources},
+    shader::Shader,
+    source::{SHADOW_FRAG_SRC, SHADOW_VERT_SRC},
+    texture::{Texture, TextureFormat},
+    uniform::Uniform,
+};
+
+pub const MAX_SHADOW_CASCADES: usize = 3;
+
+const SHADOW_MAP_NAMES: [&str; MAX_SHADOW_CASCADES] = ["shadow_0", "shadow_1", "shadow_2"];
+
+/// Half the width of the area around the camera each cascade covers, in blocks
+const CASCADE_RADII: [f32; MAX_SHADOW_CASCADES] = [24.0, 64.0, 160.0];
+
+/// How far past a cascade's area along the sun's direction casters are still drawn, so hills and
+/// trees outside the area can shade the ground inside it
+const CASTER_DEPTH: f32 = 160.0;
+
+/// Shadows cast by the sun or moon onto terrain around the camera, with one depth map per cascade
+/// and the nearest ones the most detailed
+pub(crate) struct ShadowPass {
+    resolution: i32,
+    cascades: usize,
+}
+
+impl ShadowPass {
+    pub fn new(resolution: i32, cascades: usize) -> Self {
+        Self {
+            resolution,
+            cascades: cascades.clamp(1, MAX_SHADOW_CASCADES),
+        }
+    }
+
+    pub fn init_gl_resources(&self, gl_resources: &mut GLResources) {
+        let shadow_program = Shader::new(SHADOW_VERT_SRC, SHADOW_FRAG_SRC).unwrap();
+        gl_resources.add_shader("shadow", shadow_program);
+
+        for name in &SHADOW_MAP_NAMES[..self.cascades] {
+            let depth = Texture::empty(self.resolution, self.resolution, TextureFormat::Depth);
+            gl_resources.add_framebuffer(name, Framebuffer::with_depth_texture(depth));
+        }
+    }
+
+    /// View and projection from the light onto each cascade's area around `center`
+    pub fn light_matrices(&self, center: Vector3<f32>, light_direction: Vector3<f32>) -> Vec<Matrix4<f32>> {
+        let up = if light_direction.y.abs() < 0.99 {
+            Vector3::unit_y()
+        } else {
+            Vector3::unit_z()
+        };
+        let light_view = Matrix4::look_to_rh(Point3::new(0.0, 0.0, 0.0), -light_direction.normalize(), up);
+        let center_lightspace = light_view * Vector4::new(center.x, center.y, center.z, 1.0);
+
+        CASCADE_RADII[..self.cascades]
+            .iter()
+            .map(|radius| {
+                // Move the area in whole texels so shadow edges don't crawl as the camera moves
+                let texel_size = 2.0 * radius / self.resolution as f32;
+                let x = (center_lightspace.x / texel_size).floor() * texel_size;
+                let y = (center_lightspace.y / texel_size).floor() * texel_size;
+                let depth = -center_lightspace.z;
+                ortho(
+                    x - radius,
+                    x + radius,
+                    y - radius,
+                    y + radius,
+                    depth - radius - CASTER_DEPTH,
+                    depth + radius,
+                ) * light_view
+            })
+            .collect()
+    }
+
+    /// Render every caster into the depth map of each cascade
+    pub fn draw(
+        &self,
+        gl_resources: &GLResources,
+        casters: &[&dyn GLRenderable],
+        light_matrices: &[Matrix4<f32>],
+        time: f32,
+        animations: &[Vector3<f32>],
+    ) {
+        unsafe {
+            gl::Viewport(0, 0, self.resolution, self.resolution);
+            // Thin plants are single faces, so draw both sides
+            gl::Disable(gl::CULL_FACE);
+            gl::Enable(gl::POLYGON_OFFSET_FILL);
+            gl::PolygonOffset(2.0, 4.0);
+        }
+
+        for (name, light_matrix) in SHADOW_MAP_NAMES.iter().zip(light_matrices) {
+            let framebuffer = gl_resources.get_framebuffer(name).unwrap();
+            framebuffer.bind();
+            unsafe {
+                gl::Clear(gl::DEPTH_BUFFER_BIT);
+            }
+            let uniforms: Vec<(&str, Box<dyn Uniform>)> = vec![
+                ("light_matrix", Box::new(*light_matrix)),
+                ("time", Box::new(time)),
+                ("animations", Box::new(animations.to_vec())),
+            ];
+            for caster in casters {
+                caster.draw_shadow(gl_resources, &uniforms);
+            }
+            framebuffer.unbind();
+        }
+
+        unsafe {
+            gl::Disable(gl::POLYGON_OFFSET_FILL);
+            gl::Enable(gl::CULL_FACE);
+        }
+    }
+
+    /// Bind the depth maps for the lighting pass, starting at texture unit `first_unit`, and set the
+    /// uniforms it samples them with
+    pub fn bind_for_lighting(
+        &self,
+        gl_resources: &GLResources,
+        shader: &Shader,
+        first_unit: u32,
+        light_matrices: &[Matrix4<f32>],
+    ) {
+        for (i, name) in SHADOW_MAP_NAMES[..self.cascades].iter().enumerate() {
+            let unit = first_unit + i as u32;
+            gl_resources
+                .get_framebuffer(name)
+                .unwrap()
+                .bind_render_textures_to_current_fb(&[("depth", unit)]);
+            let sampler_name = std::ffi::CString::new(format!("shadow_map_{}", i)).unwrap();
+            shader.set_texture(&sampler_name, unit as i32);
+            light_matrices[i].set_as_uniform(shader, &format!("light_matrices[{}]", i));
+            // Stay a little inside the area so PCF samples don't run off its edge
+            (CASCADE_RADII[i] * 0.9).set_as_uniform(shader, &format!("cascade_ends[{}]", i));
+        }
+        (self.cascades as f32).set_as_uniform(shader, "shadow_cascades");
+        (1.0 / self.resolution as f32).set_as_uniform(shader, "shadow_texel_size");
+    }
+}
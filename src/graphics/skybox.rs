@@ -8,6 +8,7 @@ use super::{
     shader::Shader,
     source::{SKYBOX_BITMAP, SKYBOX_FRAG_SRC, SKYBOX_VERT_SRC},
     texture::Texture,
+    texture_pack::TexturePack,
     vertex::Vertex3D,
 };
 
@@ -22,6 +23,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, -0.50),
@@ -29,6 +31,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -36,6 +39,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -43,6 +47,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, -0.50),
@@ -50,6 +55,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, -0.5),
@@ -57,6 +63,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     // Facing negative-X
     Vertex3D {
@@ -65,6 +72,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, -0.50),
@@ -72,6 +80,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.5, -0.50, 0.5),
@@ -79,6 +88,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, -0.50, 0.50),
@@ -86,6 +96,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, -0.50),
@@ -93,6 +104,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.50, -0.50, -0.50),
@@ -100,6 +112,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     // Facing positive-Y
     Vertex3D {
@@ -108,6 +121,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(0.50, -0.50, -0.50),
@@ -115,6 +129,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, 0.50),
@@ -122,6 +137,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, 0.50),
@@ -129,6 +145,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(0.50, -0.50, -0.50),
@@ -136,6 +153,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, -0.50),
@@ -143,6 +161,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     // Facing negative-Y
     Vertex3D {
@@ -151,6 +170,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -158,6 +178,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, -0.50),
@@ -165,6 +186,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -172,6 +194,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, -0.50),
@@ -179,6 +202,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, -0.50),
@@ -186,6 +210,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 1.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     // Facing positive-Z
     Vertex3D {
@@ -194,6 +219,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, -0.50),
@@ -201,6 +227,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-left
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, -0.50),
@@ -208,6 +235,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, -0.50),
@@ -215,6 +243,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-top-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, -0.50),
@@ -222,6 +251,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-left
     Vertex3D {
         position: Vector3::new(0.50, -0.50, -0.50),
@@ -229,6 +259,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(0.0, 0.0),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Front-bottom-right
     // Facing negative-Z
     Vertex3D {
@@ -237,6 +268,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, -0.50, 0.50),
@@ -244,6 +276,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-left
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -251,6 +284,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.50, -0.50, 0.50),
@@ -258,6 +292,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-bottom-right
     Vertex3D {
         position: Vector3::new(-0.50, 0.50, 0.50),
@@ -265,6 +300,7 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(HALF, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-left
     Vertex3D {
         position: Vector3::new(0.50, 0.50, 0.50),
@@ -272,14 +308,18 @@ pub(crate) const SKYBOX_VERTS: [Vertex3D; 36] = [
         tex_coords: Vector2::new(1.0, 2.0 * THIRD),
         vtype: 0,
         lighting: 0.0,
+        animation: 0,
     }, // Back-top-right
 ];
 
 pub(crate) struct Skybox;
 
 impl GLRenderable for Skybox {
-    fn init_gl_resources(&self, gl_resources: &mut super::resources::GLResources) {
-        let skybox_texture = Texture::from_dynamic_image_bytes(SKYBOX_BITMAP, ImageFormat::Png);
+    fn init_gl_resources(&self, gl_resources: &mut super::resources::GLResources, texture_pack: &TexturePack) {
+        let skybox_texture = match &texture_pack.sky {
+            Some(sky) => Texture::from_dynamic_image(sky.clone()),
+            None => Texture::from_dynamic_image_bytes(SKYBOX_BITMAP, ImageFormat::Png),
+        };
         let skybox_program = Shader::new(SKYBOX_VERT_SRC, SKYBOX_FRAG_SRC).unwrap();
         let skybox_vertices: Box<Vec<Vertex3D>> = Box::new(SKYBOX_VERTS.into());
 
@@ -3,11 +3,26 @@ pub const SCREENQUAD_VERT_SRC: &str = include_str!("../../shaders/screenquad.ver
 pub const LIGHTING_FRAG_SRC: &str = include_str!("../../shaders/lighting.frag");
 pub const COMPOSITE_FRAG_SRC: &str = include_str!("../../shaders/composite.frag");
 pub const POSTPROCESS_FRAG_SRC: &str = include_str!("../../shaders/postprocess.frag");
+pub const SSAO_FRAG_SRC: &str = include_str!("../../shaders/ssao.frag");
+pub const SSAO_BLUR_FRAG_SRC: &str = include_str!("../../shaders/ssao_blur.frag");
+
+pub const POST_TONE_MAPPING_FRAG_SRC: &str = include_str!("../../shaders/post/tone_mapping.frag");
+pub const POST_GAMMA_FRAG_SRC: &str = include_str!("../../shaders/post/gamma.frag");
+pub const POST_VIGNETTE_FRAG_SRC: &str = include_str!("../../shaders/post/vignette.frag");
+pub const POST_UNDERWATER_FRAG_SRC: &str = include_str!("../../shaders/post/underwater.frag");
 
 pub const TERRAIN_VERT_SRC: &str = include_str!("../../shaders/cube.vert");
 pub const TERRAIN_FRAG_SRC: &str = include_str!("../../shaders/cube.frag");
+pub const TERRAIN_TRANSLUCENT_FRAG_SRC: &str = include_str!("../../shaders/translucent.frag");
 pub const TERRAIN_BITMAP: &[u8] = include_bytes!("../../assets/terrain.png");
 
+pub const SHADOW_VERT_SRC: &str = include_str!("../../shaders/shadow.vert");
+pub const SHADOW_FRAG_SRC: &str = include_str!("../../shaders/shadow.frag");
+
+pub const GUI_VERT_SRC: &str = include_str!("../../shaders/gui.vert");
+pub const GUI_FRAG_SRC: &str = include_str!("../../shaders/gui.frag");
+pub const BLOCK_OVERLAY_FRAG_SRC: &str = include_str!("../../shaders/block_overlay.frag");
+
 pub const SKYBOX_VERT_SRC: &str = include_str!("../../shaders/skybox.vert");
 pub const SKYBOX_FRAG_SRC: &str = include_str!("../../shaders/skybox.frag");
 pub const SKYBOX_BITMAP: &[u8] = include_bytes!("../../assets/sky.png");
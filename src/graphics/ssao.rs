@@ -0,0 +1,242 @@
+use cgmath::{InnerSpace, Matrix4, Vector2, Vector3};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::c_str;
+
+use super::{
+    framebuffer::Framebuffer,
+    resources::GLResources,
+    shader::Shader,
+    source::{SCREENQUAD_VERT_SRC, SSAO_BLUR_FRAG_SRC, SSAO_FRAG_SRC},
+    texture::{Texture, TextureFormat},
+    uniform::Uniform,
+    vao::VertexAttributeObject,
+};
+
+/// Upper bound on kernel samples, matching `NUM_SAMPLES` in `ssao.frag`
+const MAX_KERNEL_SIZE: usize = 64;
+
+/// Width and height of the tiled rotation noise, which the blur pass averages back out
+const NOISE_SIZE: usize = 4;
+
+/// Fixed seed so the kernel and noise, and with them the look of the occlusion, are the same every run
+const KERNEL_SEED: u64 = 0x55A0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SsaoQuality {
+    Off,
+    /// Half resolution with a small kernel, for low-end phones
+    Low,
+    High,
+}
+
+impl SsaoQuality {
+    pub fn name(self) -> &'static str {
+        match self {
+            SsaoQuality::Off => "off",
+            SsaoQuality::Low => "low",
+            SsaoQuality::High => "high",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [SsaoQuality::Off, SsaoQuality::Low, SsaoQuality::High]
+            .into_iter()
+            .find(|quality| quality.name() == name)
+    }
+
+    fn kernel_size(self) -> usize {
+        match self {
+            SsaoQuality::Off => 0,
+            SsaoQuality::Low => 16,
+            SsaoQuality::High => MAX_KERNEL_SIZE,
+        }
+    }
+
+    /// Divisor applied to the screen size for the occlusion targets
+    fn resolution_divisor(self) -> i32 {
+        match self {
+            SsaoQuality::Low => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl Default for SsaoQuality {
+    fn default() -> Self {
+        if cfg!(target_os = "android") {
+            SsaoQuality::Low
+        } else {
+            SsaoQuality::High
+        }
+    }
+}
+
+/// Screen-space ambient occlusion worked out from the GBuffer, then blurred to hide the noise
+pub(crate) struct SsaoPass {
+    kernel_size: usize,
+    width: i32,
+    height: i32,
+}
+
+impl SsaoPass {
+    /// The pass for a screen of `width` by `height`, or nothing when SSAO is off
+    pub fn new(quality: SsaoQuality, width: i32, height: i32) -> Option<Self> {
+        if quality == SsaoQuality::Off {
+            return None;
+        }
+        let divisor = quality.resolution_divisor();
+        Some(Self {
+            kernel_size: quality.kernel_size(),
+            width: (width / divisor).max(1),
+            height: (height / divisor).max(1),
+        })
+    }
+
+    pub fn init_gl_resources(&self, gl_resources: &mut GLResources) {
+        let mut rng = StdRng::seed_from_u64(KERNEL_SEED);
+
+        let ssao_program = Shader::new(SCREENQUAD_VERT_SRC, SSAO_FRAG_SRC).unwrap();
+        ssao_program.use_program();
+        for (i, sample) in kernel(&mut rng, self.kernel_size).iter().enumerate() {
+            sample.set_as_uniform(&ssao_program, &format!("samples[{}]", i));
+        }
+        (self.kernel_size as f32).set_as_uniform(&ssao_program, "kernel_size");
+        (NOISE_SIZE as f32).set_as_uniform(&ssao_program, "ssao_noise_size");
+
+        let noise: Vec<Vector3<f32>> = (0..NOISE_SIZE * NOISE_SIZE)
+            .map(|_| Vector3::new(rng.gen::<f32>() * 2.0 - 1.0, rng.gen::<f32>() * 2.0 - 1.0, 0.0))
+            .collect();
+        let noise_texture = Texture::from_vector3_array(&noise, NOISE_SIZE as i32, NOISE_SIZE as i32);
+
+        let blur_program = Shader::new(SCREENQUAD_VERT_SRC, SSAO_BLUR_FRAG_SRC).unwrap();
+
+        let ssao_color = Texture::empty(self.width, self.height, TextureFormat::SingleChannel);
+        let ssao_framebuffer = Framebuffer::with_textures(&[("color", ssao_color)], None);
+        let blur_color = Texture::empty(self.width, self.height, TextureFormat::SingleChannel);
+        let blur_framebuffer = Framebuffer::with_textures(&[("color", blur_color)], None);
+
+        gl_resources.add_shader("ssao", ssao_program);
+        gl_resources.add_shader("ssao_blur", blur_program);
+        gl_resources.add_texture("ssao_noise", noise_texture);
+        gl_resources.add_framebuffer("ssao", ssao_framebuffer);
+        gl_resources.add_framebuffer("ssao_blur", blur_framebuffer);
+    }
+
+    /// Render occlusion for the GBuffer's contents into the `ssao_blur` framebuffer, leaving the
+    /// viewport at the pass's own resolution
+    pub fn draw(
+        &self,
+        gl_resources: &GLResources,
+        screenquad: &VertexAttributeObject,
+        view_matrix: Matrix4<f32>,
+        perspective_matrix: Matrix4<f32>,
+    ) {
+        let gbuffer_fbo = gl_resources.get_framebuffer("gbuffer").unwrap();
+        let ssao_fbo = gl_resources.get_framebuffer("ssao").unwrap();
+        let blur_fbo = gl_resources.get_framebuffer("ssao_blur").unwrap();
+        let resolution = Vector2::new(self.width as f32, self.height as f32);
+
+        unsafe {
+            gl::Viewport(0, 0, self.width, self.height);
+            gl::Disable(gl::DEPTH_TEST);
+        }
+
+        ssao_fbo.bind();
+        ssao_fbo.clear_color_and_depth();
+        let ssao_program = gl_resources.get_shader("ssao").unwrap();
+        ssao_program.use_program();
+        gbuffer_fbo.bind_render_textures_to_current_fb(&[("position", 0), ("normal", 1), ("albedo", 2)]);
+        gl_resources
+            .get_texture("ssao_noise")
+            .unwrap()
+            .use_as_framebuffer_texture(3);
+        ssao_program.set_texture(unsafe { c_str!("position") }, 0);
+        ssao_program.set_texture(unsafe { c_str!("normal") }, 1);
+        ssao_program.set_texture(unsafe { c_str!("albedo") }, 2);
+        ssao_program.set_texture(unsafe { c_str!("ssao_noise") }, 3);
+        view_matrix.set_as_uniform(ssao_program, "view_matrix");
+        perspective_matrix.set_as_uniform(ssao_program, "perspective_matrix");
+        resolution.set_as_uniform(ssao_program, "resolution");
+        screenquad.draw();
+        ssao_fbo.unbind();
+
+        blur_fbo.bind();
+        blur_fbo.clear_color_and_depth();
+        let blur_program = gl_resources.get_shader("ssao_blur").unwrap();
+        blur_program.use_program();
+        ssao_fbo.bind_render_textures_to_current_fb(&[("color", 0)]);
+        blur_program.set_texture(unsafe { c_str!("ssao_input") }, 0);
+        resolution.set_as_uniform(blur_program, "resolution");
+        screenquad.draw();
+        blur_fbo.unbind();
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+}
+
+/// Sample offsets in a unit hemisphere around +Z, packed closer to the origin so nearby geometry
+/// counts for more
+fn kernel(rng: &mut StdRng, kernel_size: usize) -> Vec<Vector3<f32>> {
+    (0..kernel_size)
+        .map(|i| {
+            let sample = Vector3::new(
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>() * 2.0 - 1.0,
+                rng.gen::<f32>(),
+            )
+            .normalize()
+                * rng.gen::<f32>();
+            let scale = i as f32 / kernel_size as f32;
+            sample * (0.1 + 0.9 * scale * scale)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector4;
+
+    use super::*;
+    use crate::player::camera::perspective_matrix;
+
+    /// `RADIUS` and `BIAS` in `ssao.frag`
+    const RADIUS: f32 = 0.5;
+    const BIAS: f32 = 0.025;
+
+    /// The occlusion test in `ssao.frag`, for a sample at view space depth `sample_z` where the
+    /// GBuffer holds a surface at `sample_depth`
+    fn occludes(sample_depth: f32, sample_z: f32) -> bool {
+        sample_depth <= sample_z - BIAS
+    }
+
+    #[test]
+    fn view_space_depth_increases_away_from_the_camera() {
+        let perspective = perspective_matrix(800, 600, 8.0);
+        let near = perspective * Vector4::new(0.0, 0.0, 1.0, 1.0);
+        let far = perspective * Vector4::new(0.0, 0.0, 10.0, 1.0);
+        let behind = perspective * Vector4::new(0.0, 0.0, -1.0, 1.0);
+        assert!(near.w > 0.0 && far.w > 0.0 && behind.w < 0.0);
+        assert!(near.z / near.w < far.z / far.w);
+    }
+
+    #[test]
+    fn surfaces_in_front_of_a_sample_occlude_it() {
+        assert!(occludes(4.0, 5.0));
+        assert!(!occludes(6.0, 5.0));
+        assert!(!occludes(5.0, 5.0));
+    }
+
+    #[test]
+    fn flat_wall_facing_the_camera_is_unoccluded() {
+        // Every sample sits on the camera's side of the wall, where the GBuffer only has the wall
+        let wall_depth = 10.0;
+        let kernel = kernel(&mut StdRng::seed_from_u64(KERNEL_SEED), MAX_KERNEL_SIZE);
+        for sample in kernel {
+            let sample_z = wall_depth - sample.z * RADIUS;
+            assert!(!occludes(wall_depth, sample_z));
+        }
+    }
+}
@@ -1,12 +1,60 @@
-use std::ffi::c_void;
+use std::ffi::{c_void, CStr};
 
 use cgmath::Vector3;
+use gl::types::GLenum;
+use image::RgbaImage;
+
+/// From GL_EXT_texture_filter_anisotropic, which the bindings don't include
+const TEXTURE_MAX_ANISOTROPY_EXT: GLenum = 0x84FE;
+const MAX_TEXTURE_MAX_ANISOTROPY_EXT: GLenum = 0x84FF;
+
+/// Highest anisotropy asked for when the driver allows more
+const MAX_ANISOTROPY: f32 = 16.0;
 
 #[allow(unused)]
 pub enum TextureFormat {
     Float,
     Color,
     SingleChannel,
+    /// Depth only, for shadow maps
+    Depth,
+}
+
+/// How block textures are filtered when drawn smaller than their size
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TextureQuality {
+    /// Only the full size image, sharp up close and shimmering in the distance
+    Nearest,
+    /// Blended between mip levels in the distance
+    Mipmapped,
+    /// Mipmapped, and kept sharp on surfaces seen at a glancing angle where the driver supports it
+    Anisotropic,
+}
+
+impl TextureQuality {
+    pub fn name(self) -> &'static str {
+        match self {
+            TextureQuality::Nearest => "nearest",
+            TextureQuality::Mipmapped => "mipmapped",
+            TextureQuality::Anisotropic => "anisotropic",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [TextureQuality::Nearest, TextureQuality::Mipmapped, TextureQuality::Anisotropic]
+            .into_iter()
+            .find(|quality| quality.name() == name)
+    }
+}
+
+impl Default for TextureQuality {
+    fn default() -> Self {
+        if cfg!(target_os = "android") {
+            TextureQuality::Mipmapped
+        } else {
+            TextureQuality::Anisotropic
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -16,9 +64,12 @@ pub struct Texture {
 
 impl Texture {
     pub fn from_dynamic_image_bytes(img_bytes: &[u8], format: image::ImageFormat) -> Texture {
-        let img = image::load_from_memory_with_format(img_bytes, format)
-            .unwrap()
-            .flipv();
+        Self::from_dynamic_image(image::load_from_memory_with_format(img_bytes, format).unwrap())
+    }
+
+    /// Upload an image built in memory, flipped so its top row is at the top of texture space
+    pub fn from_dynamic_image(img: image::DynamicImage) -> Texture {
+        let img = img.flipv();
         let format = match img {
             image::DynamicImage::ImageLuma8(_) => gl::RED,
             image::DynamicImage::ImageLumaA8(_) => gl::RG,
@@ -59,7 +110,60 @@ impl Texture {
         Texture { id: texture_id }
     }
 
-    pub fn _from_vector3_array(img_bytes: &[Vector3<f32>], width: i32, height: i32) -> Texture {
+    /// Upload an image along with mip levels built for it, flipped like `from_dynamic_image`. Only
+    /// the first level is used with `TextureQuality::Nearest`.
+    pub fn from_mip_levels(levels: &[RgbaImage], quality: TextureQuality) -> Texture {
+        let levels = match quality {
+            TextureQuality::Nearest => &levels[..1],
+            _ => levels,
+        };
+
+        let mut texture_id = 0;
+        unsafe {
+            gl::GenTextures(1, &mut texture_id);
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            for (level, image) in levels.iter().enumerate() {
+                let image = image::imageops::flip_vertical(image);
+                gl::TexImage2D(
+                    gl::TEXTURE_2D,
+                    level as i32,
+                    gl::RGBA as i32,
+                    image.width() as i32,
+                    image.height() as i32,
+                    0,
+                    gl::RGBA,
+                    gl::UNSIGNED_BYTE,
+                    image.as_ptr() as *const c_void,
+                );
+            }
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAX_LEVEL, levels.len() as i32 - 1);
+
+            let min_filter = match quality {
+                TextureQuality::Nearest => gl::NEAREST,
+                _ => gl::LINEAR_MIPMAP_LINEAR,
+            };
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, min_filter as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
+
+            if quality == TextureQuality::Anisotropic && anisotropic_filtering_supported() {
+                let mut max_anisotropy = 1.0;
+                gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY_EXT, &mut max_anisotropy);
+                gl::TexParameterf(
+                    gl::TEXTURE_2D,
+                    TEXTURE_MAX_ANISOTROPY_EXT,
+                    max_anisotropy.min(MAX_ANISOTROPY),
+                );
+            }
+
+            gl::BindTexture(gl::TEXTURE_2D, 0);
+        }
+
+        Texture { id: texture_id }
+    }
+
+    pub fn from_vector3_array(img_bytes: &[Vector3<f32>], width: i32, height: i32) -> Texture {
         let mut id = 0;
         unsafe {
             gl::GenTextures(1, &mut id);
@@ -97,6 +201,12 @@ impl Texture {
             TextureFormat::Float => (gl::RGB16F as i32, gl::RGB, gl::FLOAT),
             TextureFormat::Color => (gl::RGBA as i32, gl::RGBA, gl::UNSIGNED_BYTE),
             TextureFormat::SingleChannel => (gl::R16F as i32, gl::RED, gl::FLOAT),
+            TextureFormat::Depth => (gl::DEPTH_COMPONENT24 as i32, gl::DEPTH_COMPONENT, gl::UNSIGNED_INT),
+        };
+        // GLES can't filter 24-bit depth textures
+        let mag_filter = match format {
+            gl::DEPTH_COMPONENT => gl::NEAREST,
+            _ => gl::LINEAR,
         };
         texture.bind();
         unsafe {
@@ -114,7 +224,7 @@ impl Texture {
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
             gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, mag_filter as i32);
         }
         texture.unbind();
         texture
@@ -148,3 +258,21 @@ impl Texture {
         }
     }
 }
+
+/// Extensions providing anisotropic filtering, the ARB one being the same feature made core in GL 4.6
+const ANISOTROPIC_EXTENSIONS: [&[u8]; 2] =
+    [b"GL_EXT_texture_filter_anisotropic", b"GL_ARB_texture_filter_anisotropic"];
+
+/// Whether the driver lists either anisotropic filtering extension
+fn anisotropic_filtering_supported() -> bool {
+    let mut extension_count = 0;
+    unsafe {
+        gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut extension_count);
+    }
+    (0..extension_count as u32).any(|i| {
+        let extension = unsafe { gl::GetStringi(gl::EXTENSIONS, i) };
+        !extension.is_null()
+            && ANISOTROPIC_EXTENSIONS
+                .contains(&unsafe { CStr::from_ptr(extension as *const std::ffi::c_char) }.to_bytes())
+    })
+}
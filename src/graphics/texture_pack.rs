@@ -0,0 +1,191 @@
+use std::{collections::HashMap, path::Path};
+
+use image::{imageops, DynamicImage, ImageFormat};
+use json::JsonValue;
+
+use super::{
+    atlas::{placeholder_tile, AtlasTile, TextureAtlas, MAX_ANIMATIONS},
+    source::TERRAIN_BITMAP,
+};
+
+/// File in a texture pack's directory describing its contents
+pub const MANIFEST_FILE: &str = "pack.json";
+
+/// Size of a tile in the built-in terrain image
+const BUILTIN_TILE_SIZE: u32 = 16;
+
+/// Seconds each frame of an animated texture is shown for when its pack doesn't say
+const DEFAULT_FRAME_TIME: f32 = 0.1;
+
+/// Built-in block textures as (name, column, row) of their tile in the terrain image, counting rows
+/// from the top
+const BUILTIN_TILES: &[(&str, u32, u32)] = &[
+    ("grass_top", 0, 0),
+    ("stone", 1, 0),
+    ("dirt", 2, 0),
+    ("grass_side", 3, 0),
+    ("oak_planks", 4, 0),
+    ("stone_slab_side", 5, 0),
+    ("stone_slab_top", 6, 0),
+    ("rose", 12, 0),
+    ("dandelion", 13, 0),
+    ("cobblestone", 0, 1),
+    ("sand", 2, 1),
+    ("gravel", 3, 1),
+    ("oak_log_side", 4, 1),
+    ("oak_log_top", 5, 1),
+    ("iron_ore", 1, 2),
+    ("coal_ore", 2, 2),
+    ("short_grass", 7, 2),
+    ("furnace_front", 12, 2),
+    ("furnace_side", 13, 2),
+    ("glass", 1, 3),
+    ("diamond_ore", 2, 3),
+    ("oak_leaves", 4, 3),
+    ("fern", 8, 3),
+    ("furnace_front_lit", 13, 3),
+    ("furnace_top", 14, 3),
+    ("torch", 0, 5),
+];
+
+/// Block textures and sky the world is drawn with
+pub struct TexturePack {
+    pub name: String,
+    pub atlas: TextureAtlas,
+    /// Replaces the built-in sky when set
+    pub sky: Option<DynamicImage>,
+}
+
+impl TexturePack {
+    /// The textures embedded in the game
+    pub fn builtin() -> Self {
+        Self {
+            name: "Built-in".to_string(),
+            atlas: TextureAtlas::stitch(builtin_tiles().into_iter().collect()),
+            sky: None,
+        }
+    }
+
+    /// Load the pack in `directory`, described by its `pack.json` manifest:
+    ///
+    /// `{ "name": "...", "textures": { "stone": "blocks/stone.png", ... }, "sky": "sky.png" }`
+    ///
+    /// Animated textures are given as `{ "path": "water.png", "frame_time": 0.1 }`, where the image is
+    /// a strip of square frames from top to bottom, or of `"frames"` frames when set. Textures the
+    /// pack doesn't list keep their built-in image, and listed images which can't be read are
+    /// replaced with the placeholder.
+    pub fn load(directory: &Path) -> Result<Self, String> {
+        let manifest_path = directory.join(MANIFEST_FILE);
+        let manifest = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("{}: {}", manifest_path.display(), e))?;
+        let manifest = json::parse(&manifest).map_err(|e| format!("{}: {}", manifest_path.display(), e))?;
+        if !manifest.is_object() {
+            return Err("Texture pack manifest must be a JSON object".to_string());
+        }
+
+        let name = match &manifest["name"] {
+            JsonValue::Null => directory
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            value => value.as_str().ok_or("Texture pack \"name\" must be a string")?.to_string(),
+        };
+
+        let mut tiles = builtin_tiles();
+        match &manifest["textures"] {
+            JsonValue::Null => {}
+            JsonValue::Object(textures) => {
+                for (texture_name, entry) in textures.iter() {
+                    let tile = load_tile(directory, texture_name, entry)?;
+                    tiles.insert(texture_name.to_string(), tile);
+                }
+            }
+            _ => return Err("Texture pack \"textures\" must map texture names to image paths".to_string()),
+        }
+
+        let animated = tiles.values().filter(|tile| tile.frames > 1).count();
+        if animated > MAX_ANIMATIONS {
+            return Err(format!(
+                "Texture pack has {} animated textures, at most {} are supported",
+                animated, MAX_ANIMATIONS
+            ));
+        }
+
+        let sky = match &manifest["sky"] {
+            JsonValue::Null => None,
+            value => {
+                let path = value.as_str().ok_or("Texture pack \"sky\" must be an image path")?;
+                match image::open(directory.join(path)) {
+                    Ok(image) => Some(DynamicImage::ImageRgba8(image.to_rgba8())),
+                    Err(e) => {
+                        warn!("Couldn't load sky from {}: {}", path, e);
+                        None
+                    }
+                }
+            }
+        };
+
+        Ok(Self {
+            name,
+            atlas: TextureAtlas::stitch(tiles.into_iter().collect()),
+            sky,
+        })
+    }
+}
+
+/// Read the texture `entry` in a pack's manifest, either an image path or an object describing an
+/// animation
+fn load_tile(directory: &Path, texture_name: &str, entry: &JsonValue) -> Result<AtlasTile, String> {
+    let path = match entry {
+        JsonValue::Object(_) => &entry["path"],
+        _ => entry,
+    };
+    let path = path
+        .as_str()
+        .ok_or_else(|| format!("Path of texture \"{}\" must be a string", texture_name))?;
+    let image = match image::open(directory.join(path)) {
+        Ok(image) => image.to_rgba8(),
+        Err(e) => {
+            warn!("Couldn't load texture \"{}\" from {}: {}", texture_name, path, e);
+            return Ok(AtlasTile::still(placeholder_tile(BUILTIN_TILE_SIZE)));
+        }
+    };
+    if !entry.is_object() {
+        return Ok(AtlasTile::still(image));
+    }
+
+    let frames = match &entry["frames"] {
+        JsonValue::Null => image.height() / image.width().max(1),
+        value => value
+            .as_u32()
+            .ok_or_else(|| format!("Frames of texture \"{}\" must be a whole number", texture_name))?,
+    };
+    let frame_time = match &entry["frame_time"] {
+        JsonValue::Null => DEFAULT_FRAME_TIME,
+        value => value
+            .as_f32()
+            .ok_or_else(|| format!("Frame time of texture \"{}\" must be a number", texture_name))?,
+    };
+    AtlasTile::animated(image, frames, frame_time).map_err(|e| format!("Texture \"{}\": {}", texture_name, e))
+}
+
+/// Tiles cut from the embedded terrain image
+fn builtin_tiles() -> HashMap<String, AtlasTile> {
+    let terrain = image::load_from_memory_with_format(TERRAIN_BITMAP, ImageFormat::Png)
+        .unwrap()
+        .to_rgba8();
+    BUILTIN_TILES
+        .iter()
+        .map(|(name, column, row)| {
+            let tile = imageops::crop_imm(
+                &terrain,
+                column * BUILTIN_TILE_SIZE,
+                row * BUILTIN_TILE_SIZE,
+                BUILTIN_TILE_SIZE,
+                BUILTIN_TILE_SIZE,
+            )
+            .to_image();
+            (name.to_string(), AtlasTile::still(tile))
+        })
+        .collect()
+}
@@ -36,6 +36,13 @@ impl Uniform for Vector3<f32> {
     }
 }
 
+impl Uniform for Vec<Vector3<f32>> {
+    fn set_as_uniform(&self, shader: &Shader, name: &str) {
+        let c_name = CString::new(name).unwrap();
+        shader.set_vec3_array(c_name.as_c_str(), self);
+    }
+}
+
 impl Uniform for Vector2<f32> {
     fn set_as_uniform(&self, shader: &Shader, name: &str) {
         let c_name = CString::new(name).unwrap();
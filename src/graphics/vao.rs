@@ -39,9 +39,23 @@ impl VertexAttributeObject {
     }
 
     pub fn draw(&self) {
+        self.draw_range(0, self.buffer.get_length());
+    }
+
+    /// Draw `count` vertices starting from `first` as triangles
+    pub fn draw_range(&self, first: usize, count: usize) {
+        self.bind();
+        unsafe {
+            gl::DrawArrays(gl::TRIANGLES, first as i32, count as i32);
+        }
+        self.unbind();
+    }
+
+    /// Draw the buffer as separate line segments, one for each pair of vertices
+    pub fn draw_lines(&self) {
         self.bind();
         unsafe {
-            gl::DrawArrays(gl::TRIANGLES, 0, self.buffer.get_length() as i32);
+            gl::DrawArrays(gl::LINES, 0, self.buffer.get_length() as i32);
         }
         self.unbind();
     }
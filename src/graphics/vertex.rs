@@ -14,6 +14,8 @@ pub struct Vertex3D {
     pub tex_coords: Vector2<f32>,
     pub vtype: i32,
     pub lighting: f32,
+    /// Position of the texture's animation in the atlas's table plus one, 0 for still textures
+    pub animation: i32,
 }
 
 impl Default for Vertex3D {
@@ -24,6 +26,7 @@ impl Default for Vertex3D {
             tex_coords: Vector2::zero(),
             vtype: 0,
             lighting: 0.0,
+            animation: 0,
         }
     }
 }
@@ -45,11 +48,11 @@ impl VertexBufferContents for Vec<Vertex2D> {
     fn setup_for_current_vbo(&self) {
         let stride = std::mem::size_of::<Vertex2D>();
         let size = (self.len() * stride) as GLsizeiptr;
-        let data = &self[0] as *const Vertex2D as *const c_void;
+        let data = self.as_ptr() as *const c_void;
         unsafe {
             gl::BufferData(gl::ARRAY_BUFFER, size, data, gl::STATIC_DRAW);
 
-            // Vertex3D-specific attributes
+            // Vertex2D-specific attributes
             // 0 - position
             // 1 - texture coords
 
@@ -58,11 +61,11 @@ impl VertexBufferContents for Vec<Vertex2D> {
             gl::EnableVertexAttribArray(position_location);
             gl::VertexAttribPointer(
                 position_location,
-                3,
+                2,
                 gl::FLOAT,
                 gl::FALSE,
                 stride as i32,
-                offset_of!(Vertex3D, position) as *const c_void,
+                offset_of!(Vertex2D, position) as *const c_void,
             );
 
             // vertex texture coords
@@ -74,7 +77,7 @@ impl VertexBufferContents for Vec<Vertex2D> {
                 gl::FLOAT,
                 gl::FALSE,
                 stride as i32,
-                offset_of!(Vertex3D, tex_coords) as *const c_void,
+                offset_of!(Vertex2D, tex_coords) as *const c_void,
             );
         }
     }
@@ -84,7 +87,7 @@ impl VertexBufferContents for Vec<Vertex2D> {
     }
 
     fn get_raw_start_ptr(&self) -> *const c_void {
-        &self[0] as *const Vertex2D as *const c_void
+        self.as_ptr() as *const c_void
     }
 
     fn get_stride(&self) -> usize {
@@ -96,7 +99,7 @@ impl VertexBufferContents for Vec<Vertex3D> {
     fn setup_for_current_vbo(&self) {
         let stride = std::mem::size_of::<Vertex3D>();
         let size = (self.len() * stride) as GLsizeiptr;
-        let data = &self[0] as *const Vertex3D as *const c_void;
+        let data = self.as_ptr() as *const c_void;
         unsafe {
             gl::BufferData(gl::ARRAY_BUFFER, size, data, gl::STATIC_DRAW);
 
@@ -106,6 +109,7 @@ impl VertexBufferContents for Vec<Vertex3D> {
             // 2 - texture coords
             // 3 - vertex type
             // 4 - vertex lighting
+            // 5 - texture animation
 
             // vertex Positions
             let position_location = 0; //gl::GetAttribLocation(self.shader.as_ref().unwrap().id, c_str!("position").as_ptr()) as u32;
@@ -166,6 +170,18 @@ impl VertexBufferContents for Vec<Vertex3D> {
                 stride as i32,
                 offset_of!(Vertex3D, lighting) as *const c_void,
             );
+
+            // texture animation
+            let animation_location = 5;
+            gl::EnableVertexAttribArray(animation_location);
+            gl::VertexAttribPointer(
+                animation_location,
+                1,
+                gl::INT,
+                gl::FALSE,
+                stride as i32,
+                offset_of!(Vertex3D, animation) as *const c_void,
+            );
         }
     }
 
@@ -174,7 +190,7 @@ impl VertexBufferContents for Vec<Vertex3D> {
     }
 
     fn get_raw_start_ptr(&self) -> *const c_void {
-        &self[0] as *const Vertex3D as *const c_void
+        self.as_ptr() as *const c_void
     }
 
     fn get_stride(&self) -> usize {
@@ -1 +1,187 @@
 pub(crate) mod drop;
+
+use json::{object, JsonValue};
+
+use crate::{
+    player::mining::Tool,
+    terrain::block::{ToolType, BLOCKS},
+};
+
+/// Item IDs below this are block items, sharing the ID of the block they place
+pub const FIRST_NON_BLOCK_ITEM: usize = 256;
+
+const MAX_STACK_SIZE: usize = 64;
+
+pub const ITEMS: [Item; 5] = [
+    Item {
+        id: 256,
+        name: "Stick",
+        kind: ItemKind::Material,
+        max_stack: MAX_STACK_SIZE,
+    },
+    Item {
+        id: 257,
+        name: "Wooden Pickaxe",
+        kind: ItemKind::Tool(
+            Tool {
+                kind: ToolType::Pickaxe,
+                speed_multiplier: 2.0,
+            },
+            59,
+        ),
+        max_stack: 1,
+    },
+    Item {
+        id: 258,
+        name: "Stone Pickaxe",
+        kind: ItemKind::Tool(
+            Tool {
+                kind: ToolType::Pickaxe,
+                speed_multiplier: 4.0,
+            },
+            131,
+        ),
+        max_stack: 1,
+    },
+    Item {
+        id: 259,
+        name: "Wooden Axe",
+        kind: ItemKind::Tool(
+            Tool {
+                kind: ToolType::Axe,
+                speed_multiplier: 2.0,
+            },
+            59,
+        ),
+        max_stack: 1,
+    },
+    Item {
+        id: 260,
+        name: "Apple",
+        kind: ItemKind::Food,
+        max_stack: MAX_STACK_SIZE,
+    },
+];
+
+#[derive(Clone, Copy)]
+pub enum ItemKind {
+    /// Places the block with this ID
+    Block(usize),
+    /// Only used for crafting
+    Material,
+    /// A tool and how many uses it has before breaking
+    Tool(Tool, usize),
+    /// Can be eaten
+    Food,
+}
+
+#[derive(Clone, Copy)]
+pub struct Item {
+    pub id: usize,
+    pub name: &'static str,
+    pub kind: ItemKind,
+    pub max_stack: usize,
+}
+
+/// Look up the item with ID `id`, whether it places a block or not
+pub fn item_by_id(id: usize) -> Option<Item> {
+    if id < FIRST_NON_BLOCK_ITEM {
+        BLOCKS.get(id).filter(|block| block.id != 0).map(|block| Item {
+            id,
+            name: block.name,
+            kind: ItemKind::Block(block.id),
+            max_stack: MAX_STACK_SIZE,
+        })
+    } else {
+        ITEMS.get(id - FIRST_NON_BLOCK_ITEM).copied()
+    }
+}
+
+pub fn item_id_by_name(name: &str) -> Option<usize> {
+    BLOCKS
+        .iter()
+        .skip(1)
+        .find(|block| block.name == name)
+        .map(|block| block.id)
+        .or_else(|| ITEMS.iter().find(|item| item.name == name).map(|item| item.id))
+}
+
+/// A number of the same item held together in an inventory slot or a drop
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ItemStack {
+    pub item_id: usize,
+    pub count: usize,
+    /// Uses left before a tool breaks
+    pub durability: Option<usize>,
+    /// Extra per-item value, stacks only merge when this matches
+    pub data: usize,
+}
+
+impl ItemStack {
+    pub fn new(item_id: usize, count: usize) -> Self {
+        let durability = match item_by_id(item_id).map(|item| item.kind) {
+            Some(ItemKind::Tool(_, durability)) => Some(durability),
+            _ => None,
+        };
+        Self {
+            item_id,
+            count,
+            durability,
+            data: 0,
+        }
+    }
+
+    pub fn item(&self) -> Item {
+        item_by_id(self.item_id).unwrap()
+    }
+
+    /// The block this stack places, if it is a block item
+    pub fn block_id(&self) -> Option<usize> {
+        match self.item().kind {
+            ItemKind::Block(block_id) => Some(block_id),
+            _ => None,
+        }
+    }
+
+    pub fn tool(&self) -> Option<Tool> {
+        match self.item().kind {
+            ItemKind::Tool(tool, _) => Some(tool),
+            _ => None,
+        }
+    }
+
+    pub fn max_stack(&self) -> usize {
+        self.item().max_stack
+    }
+
+    /// Whether `other` can be merged into this stack
+    pub fn stacks_with(&self, other: &ItemStack) -> bool {
+        self.item_id == other.item_id
+            && self.durability == other.durability
+            && self.data == other.data
+            && self.max_stack() > 1
+    }
+
+    pub fn to_json(self) -> JsonValue {
+        let mut stack_json = object! {
+            "item" : self.item_id,
+            "count" : self.count,
+            "data" : self.data,
+        };
+        if let Some(durability) = self.durability {
+            stack_json.insert("durability", durability).unwrap();
+        }
+        stack_json
+    }
+
+    pub fn from_json(stack_json: &JsonValue) -> Option<Self> {
+        let item_id = stack_json["item"].as_usize()?;
+        item_by_id(item_id)?;
+        Some(Self {
+            item_id,
+            count: stack_json["count"].as_usize().unwrap_or(1),
+            durability: stack_json["durability"].as_usize(),
+            data: stack_json["data"].as_usize().unwrap_or(0),
+        })
+    }
+}
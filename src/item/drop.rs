@@ -7,6 +7,7 @@ use crate::{
     graphics::{
         mesh::block_drop_vertices,
         resources::{GLRenderable, GLResources},
+        texture_pack::TexturePack,
         uniform::Uniform,
     },
     physics::{
@@ -18,9 +19,11 @@ use crate::{
     terrain::block::BLOCKS,
 };
 
+use super::ItemStack;
+
 pub struct ItemDrop {
     // Persists across OpenGL context creation
-    pub block_id: usize,
+    pub stack: ItemStack,
     pub position: Vector3<f32>,
     rotation: Vector3<f32>,
     scale: Vector3<f32>,
@@ -34,9 +37,9 @@ pub struct ItemDrop {
 }
 
 impl ItemDrop {
-    pub fn new(block_id: usize, position: Vector3<f32>) -> ItemDrop {
+    pub fn new(stack: ItemStack, position: Vector3<f32>) -> ItemDrop {
         ItemDrop {
-            block_id,
+            stack,
             position,
             rotation: Vector3::zero(),
             scale: Vector3::new(0.25, 0.25, 0.25),
@@ -51,11 +54,14 @@ impl ItemDrop {
 }
 
 impl GLRenderable for ItemDrop {
-    fn init_gl_resources(&self, gl_resources: &mut GLResources) {
-        let name = format!("item_{}", self.block_id);
-        if gl_resources.get_vao(&name).is_none() {
-            let verts = Box::new(block_drop_vertices(&BLOCKS[self.block_id]));
-            gl_resources.update_vao_buffer(name, verts);
+    fn init_gl_resources(&self, gl_resources: &mut GLResources, texture_pack: &TexturePack) {
+        // Only block items have a model to draw so far
+        if let Some(block_id) = self.stack.block_id() {
+            let name = format!("item_{}", self.stack.item_id);
+            if gl_resources.get_vao(&name).is_none() {
+                let verts = Box::new(block_drop_vertices(&BLOCKS[block_id], texture_pack));
+                gl_resources.update_vao_buffer(name, verts);
+            }
         }
     }
 
@@ -80,7 +86,7 @@ impl GLRenderable for ItemDrop {
         shader.set_mat4(unsafe { c_str!("model_matrix") }, &model_matrix);
         shader.set_texture(unsafe { c_str!("texture_map") }, 0);
 
-        let name = format!("item_{}", self.block_id);
+        let name = format!("item_{}", self.stack.item_id);
         if let Some(vao) = gl_resources.get_vao(&name) {
             vao.draw();
         }
@@ -1,11 +1,16 @@
-use crate::{engine::PlayerInput, EngineLock};
+use crate::{
+    engine::{EngineSettings, GameMode, PlayerInput, WorldList},
+    EngineLock,
+};
 use jni::{
     objects::{JClass, JString},
-    sys::{jboolean, jfloat, jint, jlong, jstring},
+    sys::{jboolean, jfloat, jint, jintArray, jlong, jstring},
     JNIEnv,
 };
 use crate::engine::EngineEvent;
 
+/// Open the world saved at `save_path` and return a handle to its engine, or 0 when it can't be
+/// opened. The entry points taking the handle return straight away when it is 0.
 #[no_mangle]
 pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_initEngineNative(
     mut env: JNIEnv,
@@ -17,16 +22,118 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_initEngineNa
         android_log::init("VoxelTest").unwrap();
     }
     let save_path_rs: String = env.get_string(&save_path).unwrap().into();
-    let save_file = std::path::Path::new(&save_path_rs);
-    let engine = if save_file.exists() {
-        debug!("Restoring from save file");
-        Box::new(EngineLock::load_from_save(save_path_rs.as_str()))
-    } else {
-        debug!("Creating new world");
-        Box::new(EngineLock::default())
-    };
+    debug!("Opening world {}", save_path_rs);
+    match EngineLock::open_world(save_path_rs.as_str()) {
+        Ok(engine) => Box::into_raw(Box::new(engine)) as jlong,
+        Err(e) => {
+            error!("Couldn't open world: {}", e);
+            0
+        }
+    }
+}
+
+/// List the worlds in `worlds_path` as a JSON array of their metadata, most recently played first
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_listWorldsNative(
+    mut env: JNIEnv,
+    _: JClass,
+    worlds_path: JString,
+) -> jstring {
+    let worlds_path_rs: String = env.get_string(&worlds_path).unwrap().into();
+    let worlds: Vec<json::JsonValue> = WorldList::new(&worlds_path_rs)
+        .list()
+        .iter()
+        .map(|world| world.to_json())
+        .collect();
+    env.new_string(json::JsonValue::from(worlds).dump())
+        .expect("unable to create world list string")
+        .into_raw()
+}
+
+/// Create a world in `worlds_path` and return its ID, or null. The ID names the world's directory inside
+/// `worlds_path`, so `initEngineNative` takes `worlds_path` joined with it.
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_createWorldNative(
+    mut env: JNIEnv,
+    _: JClass,
+    worlds_path: JString,
+    name: JString,
+    seed: jlong,
+) -> jstring {
+    let worlds_path_rs: String = env.get_string(&worlds_path).unwrap().into();
+    let name_rs: String = env.get_string(&name).unwrap().into();
+    match WorldList::new(&worlds_path_rs).create(&name_rs, seed as u32) {
+        Ok(world) => env
+            .new_string(world.id)
+            .expect("unable to create world id string")
+            .into_raw(),
+        Err(e) => {
+            error!("Couldn't create world: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_renameWorldNative(
+    mut env: JNIEnv,
+    _: JClass,
+    worlds_path: JString,
+    world_id: JString,
+    name: JString,
+) -> jboolean {
+    let worlds_path_rs: String = env.get_string(&worlds_path).unwrap().into();
+    let world_id_rs: String = env.get_string(&world_id).unwrap().into();
+    let name_rs: String = env.get_string(&name).unwrap().into();
+    match WorldList::new(&worlds_path_rs).rename(&world_id_rs, &name_rs) {
+        Ok(_) => true as u8,
+        Err(e) => {
+            error!("Couldn't rename world: {}", e);
+            false as u8
+        }
+    }
+}
 
-    Box::into_raw(engine) as jlong
+/// Copy a world and return the new world's ID, or null
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_duplicateWorldNative(
+    mut env: JNIEnv,
+    _: JClass,
+    worlds_path: JString,
+    world_id: JString,
+    name: JString,
+) -> jstring {
+    let worlds_path_rs: String = env.get_string(&worlds_path).unwrap().into();
+    let world_id_rs: String = env.get_string(&world_id).unwrap().into();
+    let name_rs: String = env.get_string(&name).unwrap().into();
+    match WorldList::new(&worlds_path_rs).duplicate(&world_id_rs, &name_rs) {
+        Ok(world) => env
+            .new_string(world.id)
+            .expect("unable to create world id string")
+            .into_raw(),
+        Err(e) => {
+            error!("Couldn't duplicate world: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_deleteWorldNative(
+    mut env: JNIEnv,
+    _: JClass,
+    worlds_path: JString,
+    world_id: JString,
+) -> jboolean {
+    let worlds_path_rs: String = env.get_string(&worlds_path).unwrap().into();
+    let world_id_rs: String = env.get_string(&world_id).unwrap().into();
+    match WorldList::new(&worlds_path_rs).delete(&world_id_rs) {
+        Ok(()) => true as u8,
+        Err(e) => {
+            error!("Couldn't delete world: {}", e);
+            false as u8
+        }
+    }
 }
 
 #[no_mangle]
@@ -37,6 +144,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_initGLNative
     width: jint,
     height: jint,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let mut engine = (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.init_gl(width as i32, height as i32);
 }
@@ -47,6 +157,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_startWorkerT
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let mut engine = (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.init_engine();
     engine.start_gameloop();
@@ -59,6 +172,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_saveGameNati
     ptr: jlong,
     save_path: JString,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let save_path_rs: String = env
         .get_string(&save_path)
         .expect("unable to parse save path")
@@ -67,6 +183,47 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_saveGameNati
     engine.save_to_file(save_path_rs.as_str());
 }
 
+/// Apply settings sent as a JSON blob from the preferences screen, returning whether they were valid
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_setSettingsNative(
+    mut env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+    settings_json: JString,
+) -> jboolean {
+    if ptr == 0 {
+        return false as u8;
+    }
+    let settings_json_rs: String = env
+        .get_string(&settings_json)
+        .expect("unable to parse settings")
+        .into();
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    match EngineSettings::parse(&settings_json_rs).and_then(|settings| engine.set_settings(settings)) {
+        Ok(()) => true as u8,
+        Err(e) => {
+            error!("Rejected settings: {}", e);
+            false as u8
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getSettingsNative(
+    env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jstring {
+    if ptr == 0 {
+        return std::ptr::null_mut();
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    let settings_json = engine.settings().to_json().dump();
+    env.new_string(settings_json)
+        .expect("unable to create settings string")
+        .into_raw()
+}
+
 /*#[no_mangle]
 pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_updateNative(
     _env: JNIEnv,
@@ -83,6 +240,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_drawFrameNat
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.draw();
 }
@@ -93,6 +253,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_resetGlResou
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.reset_gl_resources();
 }
@@ -144,8 +307,11 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_lookAroundNa
     dx: jfloat,
     dy: jfloat,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
-    engine.engine_event(EngineEvent::UserInput(PlayerInput::Look(dy, dx)));
+    engine.engine_event(EngineEvent::UserInput(PlayerInput::Look(dx, dy)));
 }
 
 #[no_mangle]
@@ -157,6 +323,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_moveAroundNa
     dy: jfloat,
     dz: jfloat,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.engine_event(EngineEvent::UserInput(PlayerInput::Walk(dx, dy, dz)));
 }
@@ -167,6 +336,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_stopMovingNa
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.engine_event(EngineEvent::UserInput(PlayerInput::Stop));
 }
@@ -177,36 +349,254 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_playerJumpNa
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.engine_event(EngineEvent::UserInput(PlayerInput::Jump));
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_playerSneakNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+    sneaking: jboolean,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.engine_event(EngineEvent::UserInput(PlayerInput::Sneak(sneaking != 0)));
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_breakBlockNative(
     _env: JNIEnv,
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.engine_event(EngineEvent::UserInput(PlayerInput::Interact(false, true)));
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_stopBreakingNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.engine_event(EngineEvent::UserInput(PlayerInput::StopBreaking));
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_placeBlockNative(
     _env: JNIEnv,
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
     engine.engine_event(EngineEvent::UserInput(PlayerInput::Interact(true, false)));
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_pickBlockNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.engine_event(EngineEvent::UserInput(PlayerInput::PickBlock));
+}
+
+/// The targeted block as `[x, y, z, normal_x, normal_y, normal_z]`, or null when no block is in reach
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getTargetedBlockNative(
+    env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jintArray {
+    if ptr == 0 {
+        return std::ptr::null_mut();
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    match engine.targeted_block() {
+        Some((world_pos, hit_normal)) => {
+            let target = [
+                world_pos.x as jint,
+                world_pos.y as jint,
+                world_pos.z as jint,
+                hit_normal.x as jint,
+                hit_normal.y as jint,
+                hit_normal.z as jint,
+            ];
+            let array = env.new_int_array(target.len() as i32).expect("unable to create target array");
+            env.set_int_array_region(&array, 0, &target)
+                .expect("unable to fill target array");
+            array.into_raw()
+        }
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getGameModeNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jint {
+    if ptr == 0 {
+        return 0;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.game_mode().id() as jint
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_setGameModeNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+    game_mode: jint,
+) {
+    if ptr == 0 {
+        return;
+    }
+    if let Some(game_mode) = GameMode::from_id(game_mode as usize) {
+        let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+        engine.set_game_mode(game_mode);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getHealthNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jfloat {
+    if ptr == 0 {
+        return 0.0;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.health()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getMaxHealthNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jfloat {
+    if ptr == 0 {
+        return 0.0;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.max_health()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_isDeadNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jboolean {
+    if ptr == 0 {
+        return false as u8;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.is_dead() as u8
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_respawnNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.respawn();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getDebugOverlayNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jboolean {
+    if ptr == 0 {
+        return false as u8;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.debug_overlay() as u8
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_setDebugOverlayNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+    shown: jboolean,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.set_debug_overlay(shown != 0);
+}
+
+/// Time of day from 0 to 1, where 0 is midnight and 0.5 is noon
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_getTimeOfDayNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+) -> jfloat {
+    if ptr == 0 {
+        return 0.0;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.time_of_day()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_setTimeOfDayNative(
+    _env: JNIEnv,
+    _: JClass,
+    ptr: jlong,
+    time_of_day: jfloat,
+) {
+    if ptr == 0 {
+        return;
+    }
+    let engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
+    engine.set_time_of_day(time_of_day);
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_prevInventoryNative(
     _env: JNIEnv,
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let _engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
 }
 
@@ -216,6 +606,9 @@ pub unsafe extern "C" fn Java_org_farriswheel_voxelgame_VoxelEngine_nextInventor
     _: JClass,
     ptr: jlong,
 ) {
+    if ptr == 0 {
+        return;
+    }
     let _engine = &mut (&mut *(ptr as *mut EngineLock)).engine.lock().unwrap();
 }
 
@@ -1,3 +1,4 @@
+mod crafting;
 pub mod engine;
 mod entity;
 mod graphics;
@@ -7,7 +8,6 @@ mod physics;
 mod player;
 mod terrain;
 
-#[cfg(feature = "android-lib")]
 #[macro_use]
 extern crate log;
 #[cfg(feature = "android-lib")]
@@ -30,6 +30,15 @@ impl EngineLock {
             engine: std::sync::Mutex::new(engine::Engine::load_from_save(save_path)),
         }
     }
+
+    pub fn open_world(world_path: &str) -> Result<Self, String> {
+        Ok(Self {
+            engine: std::sync::Mutex::new(engine::Engine::open_world(
+                world_path,
+                engine::EngineSettings::default(),
+            )?),
+        })
+    }
 }
 
 impl Default for EngineLock {
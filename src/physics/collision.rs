@@ -1,3 +1,5 @@
+use std::mem::swap;
+
 use cgmath::Vector3;
 
 use crate::terrain::{BlockWorldPos, Terrain};
@@ -26,42 +28,12 @@ pub fn check_world_collision_axis(
             for block_z in (bounding_box.pos.z.floor() as isize - 1)
                 ..=((bounding_box.pos.z + bounding_box.size.z).floor() as isize + 1)
             {
-                if !world.solid_block_at_world_pos(&BlockWorldPos::new(block_x, block_y, block_z)) {
-                    continue;
-                }
-                let block_bounding_box = Rect3 {
-                    pos: Vector3::new(block_x as f32, block_y as f32, block_z as f32),
-                    size: Vector3::new(1.0, 1.0, 1.0),
-                };
-                if rect_vs_rect(&bounding_box, &block_bounding_box) {
-                    match axis {
-                        Vec3Direction::X => {
-                            let x_overlap = if bounding_box.pos.x > block_bounding_box.pos.x {
-                                (block_bounding_box.pos.x + 1.0) - bounding_box.pos.x
-                            } else {
-                                -1.0 * (bounding_box.pos.x + bounding_box.size.x
-                                    - block_bounding_box.pos.x)
-                            };
-                            return x_overlap;
-                        }
-                        Vec3Direction::Y => {
-                            let y_overlap = if bounding_box.pos.y > block_bounding_box.pos.y {
-                                (block_bounding_box.pos.y + 1.0) - bounding_box.pos.y
-                            } else {
-                                -1.0 * (bounding_box.pos.y + bounding_box.size.y
-                                    - block_bounding_box.pos.y)
-                            };
-                            return y_overlap;
-                        }
-                        Vec3Direction::Z => {
-                            let z_overlap = if bounding_box.pos.z > block_bounding_box.pos.z {
-                                (block_bounding_box.pos.z + 1.0) - bounding_box.pos.z
-                            } else {
-                                -1.0 * (bounding_box.pos.z + bounding_box.size.z
-                                    - block_bounding_box.pos.z)
-                            };
-                            return z_overlap;
-                        }
+                let block_pos = BlockWorldPos::new(block_x, block_y, block_z);
+                for block_bounding_box in world.collision_boxes_at_world_pos(&block_pos) {
+                    let overlap =
+                        check_collision_axis(axis, bounding_box.clone(), block_bounding_box);
+                    if overlap != 0.0 {
+                        return overlap;
                     }
                 }
             }
@@ -70,7 +42,32 @@ pub fn check_world_collision_axis(
     0f32
 }
 
-#[allow(unused)]
+/// How far below an entity's feet the ground may be for it to still count as standing on it
+const SUPPORT_DEPTH: f32 = 0.5;
+
+// Returns whether anything in `world` is close enough under `bounding_box` to stand on
+pub fn has_support(bounding_box: &Rect3, world: &Terrain) -> bool {
+    let below = Rect3::new(
+        bounding_box.pos - Vector3::new(0.0, SUPPORT_DEPTH, 0.0),
+        Vector3::new(bounding_box.size.x, SUPPORT_DEPTH, bounding_box.size.z),
+    );
+    for block_x in below.pos.x.floor() as isize..=(below.pos.x + below.size.x).floor() as isize {
+        for block_y in below.pos.y.floor() as isize..=(below.pos.y + below.size.y).floor() as isize {
+            for block_z in below.pos.z.floor() as isize..=(below.pos.z + below.size.z).floor() as isize {
+                let block_pos = BlockWorldPos::new(block_x, block_y, block_z);
+                if world
+                    .collision_boxes_at_world_pos(&block_pos)
+                    .iter()
+                    .any(|block_bounding_box| rect_vs_rect(&below, block_bounding_box))
+                {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
 pub fn check_collision_axis(
     axis: Vec3Direction,
     bounding_box1: Rect3,
@@ -138,72 +135,47 @@ pub fn rect_vs_rect(r1: &Rect3, r2: &Rect3) -> bool {
         && (r1.pos.z + r1.size.z) > r2.pos.z
 }
 
-/*fn ray_vs_rect(
+/// Distance along `ray_dir` at which the ray enters `target`, and the normal of the face it enters through
+pub fn ray_vs_rect(
     ray_origin: &Vector3<f32>,
     ray_dir: &Vector3<f32>,
     target: &Rect3,
-    contact_point: &mut Vector3<f32>,
-    contact_normal: &mut Vector3<f32>,
-    t_hit_near: &mut f32) -> bool {
-
-        *contact_normal = Vector3::new(0.0, 0.0, 0.0);
-        *contact_point = Vector3::new(0.0, 0.0, 0.0);
-
-        let invdir = 1.0 / ray_dir;
-
-        let mut t_near = target.pos - ray_origin;
-        t_near.x *= invdir.x;
-        t_near.y *= invdir.y;
-        t_near.z *= invdir.z;
-        let mut t_far = target.pos + target.size - ray_origin;
-        t_far.x *= invdir.x;
-        t_far.y *= invdir.y;
-        t_far.z *= invdir.z;
-
-        if t_far.x.is_nan() || t_far.y.is_nan() || t_far.z.is_nan() {
-            return false;
-        }
-        if t_near.x.is_nan() || t_near.y.is_nan() || t_near.z.is_nan() {
-            return false;
+) -> Option<(f32, BlockWorldPos)> {
+    let mut t_hit_near = f32::NEG_INFINITY;
+    let mut t_hit_far = f32::INFINITY;
+    let mut contact_normal = BlockWorldPos::new(0, 0, 0);
+
+    for axis in 0..3 {
+        let near_side = target.pos[axis];
+        let far_side = target.pos[axis] + target.size[axis];
+        if ray_dir[axis] == 0.0 {
+            if ray_origin[axis] < near_side || ray_origin[axis] > far_side {
+                return None;
+            }
+            continue;
         }
 
-        if t_near.x > t_far.x { swap(&mut t_near.x, &mut t_far.x)}
-        if t_near.y > t_far.y { swap(&mut t_near.y, &mut t_far.y)}
-        if t_near.z > t_far.z { swap(&mut t_near.z, &mut t_far.z)}
-
-        if (t_near.x > t_far.y && t_near.x > t_far.z) ||
-           (t_near.y > t_far.x && t_near.y > t_far.z) ||
-           (t_near.z > t_far.x && t_near.z > t_far.y) {return false; }
-
-        *t_hit_near = t_near.x.max(t_near.y.max(t_near.z));
-
-        let t_hit_far = t_far.x.min(t_far.y.min(t_far.z));
-
-        if t_hit_far < 0.0 {
-            return false;
+        let mut t_near = (near_side - ray_origin[axis]) / ray_dir[axis];
+        let mut t_far = (far_side - ray_origin[axis]) / ray_dir[axis];
+        let mut normal_sign = -1;
+        if t_near > t_far {
+            swap(&mut t_near, &mut t_far);
+            normal_sign = 1;
         }
 
-        *contact_point = ray_origin + *t_hit_near * ray_dir;
-
-        if t_near.x > t_near.y && t_near.x > t_near.z {
-            if invdir.x < 0.0 {
-                *contact_normal = Vector3::new(1.0, 0.0, 0.0);
-            } else {
-                *contact_normal = Vector3::new(-1.0, 0.0, 0.0);
-            }
-        } else if t_near.y > t_near.x && t_near.y > t_near.z {
-            if invdir.y < 0.0 {
-                *contact_normal = Vector3::new(0.0, 1.0, 0.0);
-            } else {
-                *contact_normal = Vector3::new(0.0, -1.0, 0.0);
-            }
-        } else if t_near.z > t_near.x && t_near.z > t_near.y {
-            if invdir.z < 0.0 {
-                *contact_normal = Vector3::new(0.0, 0.0, 1.0);
-            } else {
-                *contact_normal = Vector3::new(0.0, 0.0, -1.0);
-            }
+        if t_near > t_hit_near {
+            t_hit_near = t_near;
+            contact_normal = BlockWorldPos::new(0, 0, 0);
+            contact_normal[axis] = normal_sign;
+        }
+        t_hit_far = t_hit_far.min(t_far);
+        if t_hit_near > t_hit_far {
+            return None;
         }
+    }
 
-        true
-}*/
+    if t_hit_far < 0.0 {
+        return None;
+    }
+    Some((t_hit_near.max(0.0), contact_normal))
+}
@@ -1,21 +1,22 @@
-use crate::terrain::{BlockWorldPos, Terrain};
-use cgmath::{InnerSpace, Quaternion, Rotation, Vector3};
+use crate::terrain::{
+    block::{MeshType, BLOCKS},
+    BlockWorldPos, Terrain,
+};
+use cgmath::{InnerSpace, Vector3};
+
+use super::collision::ray_vs_rect;
 
 pub const _X_VECTOR: Vector3<f32> = Vector3::new(1.0, 0.0, 0.0);
 pub const Y_VECTOR: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
 pub const Z_VECTOR: Vector3<f32> = Vector3::new(0.0, 0.0, 1.0);
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Vec3Direction {
     X,
     Y,
     Z,
 }
 
-pub fn quaternion_rotate(vec: Vector3<f32>, angle: f32, axis: Vector3<f32>) -> Vector3<f32> {
-    Quaternion::from_sv(-angle, axis).rotate_vector(vec)
-}
-
 // Fast inverse square root <3
 // https://en.wikipedia.org/wiki/Fast_inverse_square_root
 pub fn q_rsqrt(number: f32) -> f32 {
@@ -28,12 +29,14 @@ pub fn q_rsqrt(number: f32) -> f32 {
     y
 }
 
+/// Cast a ray through the world, returning the point where it hits a block, the block's position and the
+/// normal of the face which was hit
 pub fn dda(
     world: &Terrain,
     start: &Vector3<f32>,
     dir: &Vector3<f32>,
     max_dist: f32,
-) -> Option<(Vector3<f32>, BlockWorldPos)> {
+) -> Option<(Vector3<f32>, BlockWorldPos, BlockWorldPos)> {
     let ray_dir = dir.normalize();
 
     let mut ray_unit_step_size = Vector3 {
@@ -111,28 +114,39 @@ pub fn dda(
             min_dir = Vec3Direction::Z
         }
 
+        let hit_normal;
         if min_dir == Vec3Direction::X {
             map_check.x += step.x;
+            hit_normal = BlockWorldPos::new(-step.x, 0, 0);
             dist = ray_length_1d.x;
             ray_length_1d.x += ray_unit_step_size.x;
         } else if min_dir == Vec3Direction::Y {
             map_check.y += step.y;
+            hit_normal = BlockWorldPos::new(0, -step.y, 0);
             dist = ray_length_1d.y;
             ray_length_1d.y += ray_unit_step_size.y;
         } else {
             map_check.z += step.z;
+            hit_normal = BlockWorldPos::new(0, 0, -step.z);
             dist = ray_length_1d.z;
             ray_length_1d.z += ray_unit_step_size.z;
         }
         if world.collision_at_world_pos(&map_check) {
-            return Some((
-                start + ray_dir * dist,
-                Vector3 {
-                    x: map_check.x,
-                    y: map_check.y,
-                    z: map_check.z,
-                },
-            ));
+            // Shaped blocks are only hit if the ray passes through one of their boxes
+            if let MeshType::Model(_) = BLOCKS[world.block_at_world_pos(&map_check)].mesh_type {
+                let nearest_hit = world
+                    .selection_boxes_at_world_pos(&map_check)
+                    .iter()
+                    .filter_map(|model_box| ray_vs_rect(start, &ray_dir, model_box))
+                    .min_by(|a, b| a.0.total_cmp(&b.0));
+                if let Some((box_dist, box_normal)) = nearest_hit {
+                    if box_dist < max_dist {
+                        return Some((start + ray_dir * box_dist, map_check, box_normal));
+                    }
+                }
+            } else {
+                return Some((start + ray_dir * dist, map_check, hit_normal));
+            }
         }
     }
     None
@@ -1,18 +1,42 @@
 pub(crate) mod camera;
-mod inventory;
+pub(crate) mod game_mode;
+pub(crate) mod health;
+pub(crate) mod inventory;
+pub(crate) mod mining;
 
-use cgmath::{InnerSpace, Matrix4, Vector2, Vector3};
+use cgmath::{InnerSpace, Matrix4, Vector3};
 
-use camera::Camera;
+use camera::{Camera, LookSettings};
 
-use crate::engine::EngineEvent;
+use crate::crafting::Recipe;
+use crate::item::ItemStack;
+use crate::engine::{EngineEvent, EngineSettings};
 use crate::physics::collision::{self, Collider, Rect3};
 use crate::physics::physics_update::PhysicsUpdate;
-use crate::physics::vectormath::{q_rsqrt, Vec3Direction, Y_VECTOR};
+use crate::physics::vectormath::{self, q_rsqrt, Vec3Direction, Y_VECTOR};
+use crate::terrain::{BlockWorldPos, Terrain};
 
+use self::game_mode::GameMode;
+use self::health::{DamageSource, Health};
 use self::inventory::Inventory;
+use self::mining::{Mining, Tool};
 
-const GRAVITY_MODIFIER: f32 = 1.0;
+/// Speed of climbing and descending while flying
+const FLY_VERTICAL_SPEED: f32 = 6.0;
+
+/// How far sneaking lowers the camera and the top of the collision box
+const SNEAK_HEIGHT_OFFSET: f32 = 0.3;
+
+const SNEAK_SPEED_MULTIPLIER: f32 = 0.3;
+
+/// Gap between jump inputs after which the next one counts as a new press rather than a held key
+const JUMP_RELEASE_TIME: f32 = 0.08;
+
+/// Two jump presses within this many seconds toggle flight in creative mode
+const DOUBLE_JUMP_TIME: f32 = 0.35;
+
+/// Furthest a block can be from the camera and still be broken, placed against or picked
+pub(crate) const REACH_DISTANCE: f32 = 6.0;
 
 pub(crate) const GRAVITY: Vector3<f32> = Vector3 {
     x: 0.0,
@@ -22,10 +46,14 @@ pub(crate) const GRAVITY: Vector3<f32> = Vector3 {
 
 #[derive(Debug)]
 pub enum PlayerInput {
+    /// Raw horizontal and vertical look input, scaled by the look settings
     Look(f32, f32),
     Walk(f32, f32, f32),
     Inventory(usize),
     Interact(bool, bool),
+    StopBreaking,
+    PickBlock,
+    Sneak(bool),
     Jump,
     Sprint,
     Stop,
@@ -33,6 +61,7 @@ pub enum PlayerInput {
 
 pub(crate) struct Player {
     pub(crate) camera: Camera,
+    pub(crate) look_settings: LookSettings,
     pub position: Vector3<f32>,
     velocity: Vector3<f32>,
     acceleration: Vector3<f32>,
@@ -40,21 +69,33 @@ pub(crate) struct Player {
 
     move_speed: f32,
     speed_multiplier: f32,
+    sprint_multiplier: f32,
+    gravity: f32,
     pub grounded: bool,
     walking: bool,
     time_walking: f32,
     running: bool,
+    sneaking: bool,
     height: f32,
 
+    game_mode: GameMode,
+    flying: bool,
+    vertical_input: f32,
+    time_since_jump: f32,
+    time_since_jump_press: f32,
+
     collision_box: Rect3,
 
-    inventory: Inventory,
+    pub(crate) inventory: Inventory,
+    pub(crate) mining: Mining,
+    pub(crate) health: Health,
 }
 
 impl Player {
     pub fn new(position: Vector3<f32>, forward: Vector3<f32>) -> Self {
         Self {
             camera: Camera::new(position, forward),
+            look_settings: LookSettings::default(),
             position,
 
             velocity: Vector3::new(0f32, 0f32, 0f32),
@@ -63,22 +104,36 @@ impl Player {
 
             move_speed: 4.0,
             speed_multiplier: 2.0,
+            sprint_multiplier: 2.0,
+            gravity: GRAVITY.y,
             running: false,
+            sneaking: false,
             grounded: false,
             walking: false,
             time_walking: 0.0,
             height: 1.6,
 
+            game_mode: GameMode::Survival,
+            flying: false,
+            vertical_input: 0.0,
+            time_since_jump: f32::INFINITY,
+            time_since_jump_press: f32::INFINITY,
+
             collision_box: Rect3::new([-0.25, 0.0, -0.25].into(), [0.5, 1.6, 0.5].into()),
 
             inventory: Inventory::new(),
+            mining: Mining::new(),
+            health: Health::new(),
         }
     }
 
     pub fn input(&mut self, input: PlayerInput) {
+        if self.health.is_dead() {
+            return;
+        }
         match input {
             PlayerInput::Look(dx, dy) => {
-                self.look_direction(Vector2::new(dx, dy));
+                self.camera.look(dx, dy, &self.look_settings);
             }
             PlayerInput::Walk(dx, dy, dz) => {
                 self.move_direction(Vector3::new(dx, dy, dz));
@@ -92,19 +147,85 @@ impl Player {
             PlayerInput::Inventory(selected) => {
                 self.select_inventory(selected);
             }
+            PlayerInput::StopBreaking => {
+                self.mining.active = false;
+                self.mining.reset();
+            }
+            PlayerInput::Sneak(sneaking) => {
+                self.sneaking = sneaking;
+            }
             PlayerInput::Sprint => {
                 self.running = true;
-                self.speed_multiplier = 2.0;
+                self.speed_multiplier = self.sprint_multiplier;
             }
             _ => {}
         }
     }
 
+    /// Height of the player's eyes above their feet
+    pub fn height(&self) -> f32 {
+        if self.is_sneaking() {
+            self.height - SNEAK_HEIGHT_OFFSET
+        } else {
+            self.height
+        }
+    }
+
+    /// Sneaking has no effect while flying, where the same input is used to descend
+    pub fn is_sneaking(&self) -> bool {
+        self.sneaking && !self.flying
+    }
+
+    /// Pick up the movement and look tunables from `settings`
+    pub fn apply_settings(&mut self, settings: &EngineSettings) {
+        self.move_speed = settings.move_speed;
+        self.sprint_multiplier = settings.sprint_multiplier;
+        self.height = settings.player_height;
+        self.gravity = -settings.gravity;
+        self.look_settings = settings.look;
+    }
+
     pub fn camera_pos_and_dir(&self) -> (Vector3<f32>, Vector3<f32>) {
         (self.camera.position, self.camera.forward)
     }
 
+    /// The block the camera is looking at within reach, and the normal of the face the look ray hits
+    pub fn targeted_block(&self, terrain: &Terrain) -> Option<(BlockWorldPos, BlockWorldPos)> {
+        vectormath::dda(terrain, &self.camera.position, &self.camera.forward, REACH_DISTANCE)
+            .map(|(_world_pos, world_index, hit_normal)| (world_index, hit_normal))
+    }
+
+    pub fn game_mode(&self) -> GameMode {
+        self.game_mode
+    }
+
+    pub fn set_game_mode(&mut self, game_mode: GameMode) {
+        self.game_mode = game_mode;
+        self.flying = game_mode == GameMode::Spectator;
+        self.velocity.y = 0.0;
+        self.grounded = false;
+        self.mining.active = false;
+        self.mining.reset();
+    }
+
+    pub fn is_flying(&self) -> bool {
+        self.flying
+    }
+
+    pub fn set_flying(&mut self, flying: bool) {
+        self.flying = match self.game_mode {
+            GameMode::Survival => false,
+            GameMode::Creative => flying,
+            GameMode::Spectator => true,
+        };
+    }
+
     fn move_direction(&mut self, direction: Vector3<f32>) {
+        self.vertical_input = direction.y;
+        if direction.x == 0.0 && direction.z == 0.0 {
+            self.walking = false;
+            return;
+        }
         self.walking = true;
         self.velocity.x += direction.x;
         self.velocity.z += direction.z;
@@ -114,13 +235,19 @@ impl Player {
             q_rsqrt(self.velocity.x * self.velocity.x + self.velocity.z * self.velocity.z);
     }
 
-    fn look_direction(&mut self, direction: Vector2<f32>) {
-        self.camera.rotate_on_x_axis(direction.x);
-        self.camera.rotate_on_y_axis(direction.y);
-    }
-
     fn jump(&mut self) {
-        if self.grounded {
+        if self.time_since_jump > JUMP_RELEASE_TIME {
+            if self.game_mode == GameMode::Creative && self.time_since_jump_press < DOUBLE_JUMP_TIME {
+                self.set_flying(!self.flying);
+                self.velocity.y = 0.0;
+                self.time_since_jump_press = f32::INFINITY;
+            } else {
+                self.time_since_jump_press = 0.0;
+            }
+        }
+        self.time_since_jump = 0.0;
+
+        if self.grounded && !self.flying {
             self.velocity.y += 7f32;
             self.grounded = false;
         }
@@ -131,6 +258,7 @@ impl Player {
         self.time_walking %= 2.0 * std::f32::consts::PI / 10.0;
         self.running = false;
         self.speed_multiplier = 1.0;
+        self.vertical_input = 0.0;
     }
 
     pub fn camera_view_matrix(&self) -> Matrix4<f32> {
@@ -139,20 +267,70 @@ impl Player {
 
     pub fn select_inventory(&mut self, selected: usize) {
         self.inventory.set_selected(selected);
-        self.inventory.print_inventory();
     }
 
-    pub fn _add_to_inventory(&mut self, block_id: usize) {
-        self.inventory.add_to_inventory(block_id);
+    /// The tool in the player's hand, used to speed up mining
+    pub fn held_tool(&self) -> Option<Tool> {
+        self.inventory.selected().and_then(|stack| stack.tool())
+    }
+
+    /// Block ID of the held item, if it's a block
+    pub fn held_block(&self) -> Option<usize> {
+        self.inventory.selected()?.block_id()
+    }
+
+    /// Take one of the held block item to place, returning its block ID. Creative mode places
+    /// without using anything up.
+    pub fn take_held_block(&mut self) -> Option<usize> {
+        let block_id = self.held_block()?;
+        if self.game_mode == GameMode::Creative {
+            return Some(block_id);
+        }
+        self.inventory
+            .consume_currently_selected()
+            .and_then(|stack| stack.block_id())
+    }
+
+    /// Move the player to `position`, dropping any speed they had
+    pub fn teleport(&mut self, position: Vector3<f32>) {
+        self.position = position;
+        self.velocity = Vector3::new(0f32, 0f32, 0f32);
+        self.grounded = false;
+        self.camera.translate(self.position + self.height() * Y_VECTOR);
+    }
+
+    /// Bring the player back to life at `spawn_point`, keeping their inventory
+    pub fn respawn(&mut self, spawn_point: Vector3<f32>) {
+        self.teleport(spawn_point);
+        self.flying = self.game_mode == GameMode::Spectator;
+        self.stop_move();
+        self.mining.active = false;
+        self.mining.reset();
+        self.health.reset();
+    }
+
+    pub fn craft(&mut self, recipe: &Recipe) -> bool {
+        self.inventory.craft(recipe)
+    }
+
+    pub fn add_to_inventory(&mut self, stack: ItemStack) -> bool {
+        self.inventory.add_to_inventory(stack)
     }
 }
 
 impl PhysicsUpdate for Player {
     fn update_physics(&mut self, delta_time: f32) {
         self.camera
-            .translate(self.position + self.height * Y_VECTOR);
-        if !self.grounded {
-            self.acceleration.y = GRAVITY.y * GRAVITY_MODIFIER;
+            .translate(self.position + self.height() * Y_VECTOR);
+        self.camera.update_rotation(delta_time, &self.look_settings);
+        self.time_since_jump += delta_time;
+        self.time_since_jump_press += delta_time;
+
+        if self.flying {
+            self.acceleration.y = 0.0;
+            self.velocity.y = self.vertical_input * FLY_VERTICAL_SPEED;
+        } else if !self.grounded {
+            self.acceleration.y = self.gravity;
         }
 
         if !self.walking {
@@ -168,7 +346,9 @@ impl PhysicsUpdate for Player {
         self.velocity += self.acceleration * delta_time;
 
         let forward = Vector3::new(self.camera.forward.x, 0.0, self.camera.forward.z).normalize();
-        let move_speed = if self.running {
+        let move_speed = if self.is_sneaking() {
+            self.move_speed * SNEAK_SPEED_MULTIPLIER
+        } else if self.running {
             self.move_speed * self.speed_multiplier
         } else {
             self.move_speed
@@ -193,6 +373,9 @@ impl Collider for Player {
     fn bounding_box(&self) -> collision::Rect3 {
         let mut bounding_box = self.collision_box.clone();
         bounding_box.pos += self.position;
+        if self.is_sneaking() {
+            bounding_box.size.y -= SNEAK_HEIGHT_OFFSET;
+        }
         bounding_box
     }
 
@@ -208,10 +391,17 @@ impl Collider for Player {
             Vec3Direction::Y => {
                 self.position.y += overlap;
                 if overlap.abs() > 0.0 {
-                    self.velocity.y = 0f32;
                     if overlap > 0.0 {
+                        if self.game_mode.takes_damage() {
+                            let fall_damage =
+                                Health::fall_damage(-self.velocity.y, self.gravity);
+                            self.health.damage(fall_damage, DamageSource::Fall);
+                        }
+                        // Touching down ends creative flight
+                        self.flying = false;
                         self.grounded = true;
                     }
+                    self.velocity.y = 0f32;
                 }
             }
             Vec3Direction::Z => {
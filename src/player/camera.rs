@@ -1,23 +1,61 @@
 pub(crate) use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
 
-use crate::physics::vectormath::{quaternion_rotate, Y_VECTOR};
+use crate::physics::vectormath::Y_VECTOR;
+
+/// Furthest the camera can look up or down, just short of vertical where the view would flip
+const MAX_PITCH: f32 = 89.0 * std::f32::consts::PI / 180.0;
+
+/// How look input turns the camera
+#[derive(Clone, Copy, Debug)]
+pub struct LookSettings {
+    /// Radians turned per unit of look input
+    pub sensitivity: f32,
+    pub invert_y: bool,
+    /// Seconds the camera takes to catch up most of the way with the input, 0 to turn instantly
+    pub smoothing: f32,
+}
+
+impl Default for LookSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.002,
+            invert_y: false,
+            smoothing: 0.0,
+        }
+    }
+}
 
 pub struct Camera {
     pub position: Vector3<f32>,
     pub forward: Vector3<f32>,
     pub right: Vector3<f32>,
     pub up: Vector3<f32>,
+
+    /// Rotation around the vertical axis in radians, 0 facing +Z and increasing towards +X
+    yaw: f32,
+    /// Rotation above the horizon in radians
+    pitch: f32,
+    /// Orientation the camera is turning towards when smoothing is on
+    target_yaw: f32,
+    target_pitch: f32,
 }
 
 impl Camera {
     pub fn new(position: Vector3<f32>, direction: Vector3<f32>) -> Self {
+        let direction = direction.normalize();
+        let yaw = direction.x.atan2(direction.z);
+        let pitch = direction.y.asin();
         let mut s = Self {
             position,
             forward: direction,
             right: Vector3::new(0.0, 0.0, 0.0),
             up: Vector3::new(0.0, 0.0, 0.0),
+            yaw,
+            pitch,
+            target_yaw: yaw,
+            target_pitch: pitch,
         };
-        s.calculate_normals();
+        s.set_orientation(yaw, pitch);
         s
     }
 
@@ -52,13 +90,49 @@ impl Camera {
         self.position = new_position;
     }
 
-    pub fn rotate_on_y_axis(&mut self, angle: f32) {
-        self.forward = quaternion_rotate(self.forward, angle, self.up);
-        self.calculate_normals();
+    pub fn yaw(&self) -> f32 {
+        self.yaw
+    }
+
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Face the camera in the given direction straight away
+    pub fn set_orientation(&mut self, yaw: f32, pitch: f32) {
+        self.target_yaw = yaw;
+        self.target_pitch = pitch.clamp(-MAX_PITCH, MAX_PITCH);
+        self.yaw = self.target_yaw;
+        self.pitch = self.target_pitch;
+        self.update_forward();
+    }
+
+    /// Turn towards the camera's new orientation by `dx` horizontal and `dy` vertical look input,
+    /// where positive `dy` looks down unless inverted
+    pub fn look(&mut self, dx: f32, dy: f32, settings: &LookSettings) {
+        let dy = if settings.invert_y { -dy } else { dy };
+        self.target_yaw += dx * settings.sensitivity;
+        self.target_pitch = (self.target_pitch - dy * settings.sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
     }
 
-    pub fn rotate_on_x_axis(&mut self, angle: f32) {
-        self.forward = quaternion_rotate(self.forward, angle, self.right);
+    /// Move the camera's orientation towards where it was last told to look
+    pub fn update_rotation(&mut self, delta_time: f32, settings: &LookSettings) {
+        let t = if settings.smoothing > 0.0 {
+            1.0 - (-delta_time / settings.smoothing).exp()
+        } else {
+            1.0
+        };
+        self.yaw += (self.target_yaw - self.yaw) * t;
+        self.pitch += (self.target_pitch - self.pitch) * t;
+        self.update_forward();
+    }
+
+    fn update_forward(&mut self) {
+        self.forward = Vector3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        );
         self.calculate_normals();
     }
 
@@ -0,0 +1,51 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    /// Takes damage, collects what it breaks and spends what it places
+    Survival,
+    /// Can fly, breaks blocks instantly and places from an infinite palette
+    Creative,
+    /// Always flies through terrain without touching anything
+    Spectator,
+}
+
+impl GameMode {
+    const ALL: [GameMode; 3] = [GameMode::Survival, GameMode::Creative, GameMode::Spectator];
+
+    pub fn id(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_id(id: usize) -> Option<Self> {
+        Self::ALL.get(id).copied()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+            GameMode::Spectator => "spectator",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|mode| mode.name() == name)
+    }
+
+    /// The mode after this one, for cycling through them with a single key
+    pub fn next(self) -> Self {
+        Self::ALL[(self.id() + 1) % Self::ALL.len()]
+    }
+
+    pub fn takes_damage(self) -> bool {
+        self == GameMode::Survival
+    }
+
+    /// Whether the player can break, place and use blocks
+    pub fn can_interact(self) -> bool {
+        self != GameMode::Spectator
+    }
+
+    pub fn has_collision(self) -> bool {
+        self != GameMode::Spectator
+    }
+}
@@ -0,0 +1,93 @@
+pub const MAX_HEALTH: f32 = 20.0;
+
+/// Blocks the player can fall without getting hurt, each block beyond costs one point of health
+const SAFE_FALL_DISTANCE: f32 = 3.0;
+
+/// Seconds the player can stay under a fluid before drowning
+const MAX_AIR: f32 = 10.0;
+
+/// Points of health lost per second with the head inside a solid block
+const SUFFOCATION_DAMAGE_PER_SECOND: f32 = 2.0;
+
+/// Points of health lost per second once the player has run out of air
+const DROWNING_DAMAGE_PER_SECOND: f32 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DamageSource {
+    Fall,
+    Suffocation,
+    Drowning,
+}
+
+pub(crate) struct Health {
+    points: f32,
+    air: f32,
+    last_damage: Option<DamageSource>,
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self {
+            points: MAX_HEALTH,
+            air: MAX_AIR,
+            last_damage: None,
+        }
+    }
+
+    pub fn points(&self) -> f32 {
+        self.points
+    }
+
+    pub fn set_points(&mut self, points: f32) {
+        self.points = points.clamp(0.0, MAX_HEALTH);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.points <= 0.0
+    }
+
+    /// What last hurt the player, which is what killed them if they are dead
+    pub fn last_damage(&self) -> Option<DamageSource> {
+        self.last_damage
+    }
+
+    pub fn damage(&mut self, amount: f32, source: DamageSource) {
+        if amount <= 0.0 || self.is_dead() {
+            return;
+        }
+        self.points = (self.points - amount).max(0.0);
+        self.last_damage = Some(source);
+    }
+
+    /// Damage from landing while falling at `impact_speed`, worked out from the height fallen
+    pub fn fall_damage(impact_speed: f32, gravity: f32) -> f32 {
+        let fall_distance = impact_speed * impact_speed / (2.0 * gravity.abs());
+        (fall_distance - SAFE_FALL_DISTANCE).max(0.0).floor()
+    }
+
+    /// Apply damage over time from where the player's head is
+    pub fn update(&mut self, head_in_solid: bool, head_in_fluid: bool, delta_time: f32) {
+        if head_in_solid {
+            self.damage(
+                SUFFOCATION_DAMAGE_PER_SECOND * delta_time,
+                DamageSource::Suffocation,
+            );
+        }
+
+        if head_in_fluid {
+            self.air = (self.air - delta_time).max(0.0);
+            if self.air <= 0.0 {
+                self.damage(
+                    DROWNING_DAMAGE_PER_SECOND * delta_time,
+                    DamageSource::Drowning,
+                );
+            }
+        } else {
+            self.air = MAX_AIR;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
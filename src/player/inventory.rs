@@ -1,9 +1,12 @@
-use crate::terrain::block::BLOCKS;
+use json::JsonValue;
+
+use crate::{crafting::Recipe, item::ItemStack};
 
 const INVENTORY_SIZE: usize = 9;
 
+#[derive(Clone)]
 pub struct Inventory {
-    items: [Option<(usize, usize)>; INVENTORY_SIZE],
+    items: [Option<ItemStack>; INVENTORY_SIZE],
     selected: usize,
 }
 
@@ -15,60 +18,158 @@ impl Inventory {
         }
     }
 
-    pub fn add_to_inventory(&mut self, id_to_add: usize) {
-        for i in 0..self.items.len() {
-            if let Some((id, quantity)) = &mut self.items[i] {
-                if *id == id_to_add {
-                    *quantity += 1;
-                    return;
+    pub fn add_to_inventory(&mut self, stack: ItemStack) -> bool {
+        self.insert(stack)
+    }
+
+    /// Add `stack`, topping up matching stacks before using free slots. Returns false, leaving the
+    /// inventory unchanged, if it doesn't all fit.
+    pub fn insert(&mut self, stack: ItemStack) -> bool {
+        let mut items = self.items;
+        let mut remaining = stack.count;
+        for held in items.iter_mut().flatten() {
+            if remaining > 0 && held.stacks_with(&stack) {
+                let added = remaining.min(held.max_stack().saturating_sub(held.count));
+                held.count += added;
+                remaining -= added;
+            }
+        }
+        for slot in items.iter_mut() {
+            if remaining > 0 && slot.is_none() {
+                let added = remaining.min(stack.max_stack());
+                *slot = Some(ItemStack {
+                    count: added,
+                    ..stack
+                });
+                remaining -= added;
+            }
+        }
+
+        if remaining > 0 {
+            return false;
+        }
+        self.items = items;
+        true
+    }
+
+    /// Total count of item `item_id` held
+    pub fn count(&self, item_id: usize) -> usize {
+        self.items
+            .iter()
+            .flatten()
+            .filter(|stack| stack.item_id == item_id)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// Take `count` of item `item_id` out of the inventory. Returns false, leaving the inventory
+    /// unchanged, if there aren't enough.
+    pub fn remove(&mut self, item_id: usize, count: usize) -> bool {
+        if self.count(item_id) < count {
+            return false;
+        }
+        let mut remaining = count;
+        for slot in self.items.iter_mut() {
+            if let Some(stack) = slot {
+                if stack.item_id == item_id && remaining > 0 {
+                    let taken = remaining.min(stack.count);
+                    stack.count -= taken;
+                    remaining -= taken;
+                    if stack.count == 0 {
+                        *slot = None;
+                    }
                 }
             }
         }
+        true
+    }
+
+    pub fn has_ingredients(&self, recipe: &Recipe) -> bool {
+        recipe
+            .ingredients()
+            .iter()
+            .all(|(item_id, count)| self.count(*item_id) >= *count)
+    }
 
-        let mut first_free_index = 0;
-        for i in 0..self.items.len() {
-            if let Some(_item) = &self.items[i] {
-                first_free_index += 1;
+    /// Consume the ingredients of `recipe` and add its result, either completely or not at all
+    pub fn craft(&mut self, recipe: &Recipe) -> bool {
+        let mut crafted = self.clone();
+        for (item_id, count) in recipe.ingredients() {
+            if !crafted.remove(item_id, count) {
+                return false;
             }
         }
-        if first_free_index < self.items.len() {
-            self.items[first_free_index] = Some((id_to_add, 1));
+        if !crafted.insert(recipe.result) {
+            return false;
         }
+        *self = crafted;
+        true
     }
 
-    pub fn _consume_currently_selected(&mut self) -> Option<usize> {
-        if let Some((id, quantity)) = &mut self.items[self.selected] {
-            let id = *id;
-            *quantity -= 1;
-            if *quantity == 0 {
+    pub fn selected(&self) -> Option<&ItemStack> {
+        self.items[self.selected].as_ref()
+    }
+
+    /// Put `stack` in the selected slot, replacing whatever was there
+    pub fn set_selected_stack(&mut self, stack: ItemStack) {
+        self.items[self.selected] = Some(stack);
+    }
+
+    /// Take one item from the selected stack
+    pub fn consume_currently_selected(&mut self) -> Option<ItemStack> {
+        if let Some(stack) = &mut self.items[self.selected] {
+            let taken = ItemStack { count: 1, ..*stack };
+            stack.count -= 1;
+            if stack.count == 0 {
                 self.items[self.selected] = None;
             }
-            return Some(id);
+            return Some(taken);
         }
         None
     }
 
+    /// Use up one point of durability of the selected tool, which breaks when none is left
+    pub fn damage_selected(&mut self) {
+        if let Some(stack) = &mut self.items[self.selected] {
+            if let Some(durability) = &mut stack.durability {
+                *durability = durability.saturating_sub(1);
+                if *durability == 0 {
+                    self.items[self.selected] = None;
+                }
+            }
+        }
+    }
+
     pub fn set_selected(&mut self, selected: usize) {
         self.selected = selected % self.items.len();
     }
 
-    pub fn print_inventory(&self) {
-        print!("[");
-        for i in 0..self.items.len() {
-            let label = match self.items[i] {
-                Some((block_id, quantity)) => {
-                    format!(
-                        "{}{} ({}){}",
-                        if self.selected == i { "[" } else { "" },
-                        BLOCKS[block_id].name,
-                        quantity,
-                        if self.selected == i { "]" } else { "" }
-                    )
-                }
-                None => " ".to_string(),
-            };
-            print!(" {}", label);
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.items
+    }
+
+    pub fn selected_slot(&self) -> usize {
+        self.selected
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut inventory_json = JsonValue::new_object();
+        let items: Vec<JsonValue> = self
+            .items
+            .iter()
+            .map(|slot| slot.map(ItemStack::to_json).unwrap_or(JsonValue::Null))
+            .collect();
+        inventory_json.insert("items", items).unwrap();
+        inventory_json.insert("selected", self.selected).unwrap();
+        inventory_json
+    }
+
+    pub fn from_json(inventory_json: &JsonValue) -> Self {
+        let mut inventory = Self::new();
+        for (slot, stack_json) in inventory.items.iter_mut().zip(inventory_json["items"].members()) {
+            *slot = ItemStack::from_json(stack_json);
         }
-        println!(" ]");
+        inventory.set_selected(inventory_json["selected"].as_usize().unwrap_or(0));
+        inventory
     }
 }
@@ -0,0 +1,91 @@
+use crate::terrain::{
+    block::{Block, ToolType},
+    BlockWorldPos,
+};
+
+/// Seconds per point of hardness to break a block by hand
+const SECONDS_PER_HARDNESS: f32 = 1.5;
+
+/// Number of crack overlay textures drawn over a block as it is broken
+pub const CRACK_STAGES: usize = 10;
+
+/// A tool held while mining, speeding up blocks which prefer its kind
+#[derive(Clone, Copy)]
+pub struct Tool {
+    pub kind: ToolType,
+    pub speed_multiplier: f32,
+}
+
+/// Progress towards breaking the block the player is holding the break button on
+pub(crate) struct Mining {
+    pub(crate) active: bool,
+    target: Option<(BlockWorldPos, usize)>,
+    progress: f32,
+}
+
+impl Mining {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            target: None,
+            progress: 0.0,
+        }
+    }
+
+    /// Seconds it takes to break `block` while holding `tool`
+    pub fn break_time(block: &Block, tool: Option<Tool>) -> f32 {
+        let speed = match tool {
+            Some(tool) if tool.kind == block.tool && block.tool != ToolType::None => {
+                tool.speed_multiplier
+            }
+            _ => 1.0,
+        };
+        block.hardness * SECONDS_PER_HARDNESS / speed
+    }
+
+    /// Advance mining of `target`, the looked-at block position and its ID, returning the target once it
+    /// has been broken. Progress starts over whenever the target changes.
+    pub fn update(
+        &mut self,
+        target: Option<(BlockWorldPos, usize)>,
+        block: &Block,
+        tool: Option<Tool>,
+        delta_time: f32,
+    ) -> Option<(BlockWorldPos, usize)> {
+        if !self.active || target.is_none() {
+            self.reset();
+            return None;
+        }
+        if self.target != target {
+            self.target = target;
+            self.progress = 0.0;
+        }
+
+        let break_time = Self::break_time(block, tool);
+        self.progress += if break_time > 0.0 {
+            delta_time / break_time
+        } else {
+            1.0
+        };
+
+        if self.progress >= 1.0 {
+            self.reset();
+            target
+        } else {
+            None
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.target = None;
+        self.progress = 0.0;
+    }
+
+    /// The block being broken and which crack overlay stage to draw over it
+    pub fn crack_stage(&self) -> Option<(BlockWorldPos, usize)> {
+        self.target.map(|(world_pos, _)| {
+            let stage = (self.progress * CRACK_STAGES as f32) as usize;
+            (world_pos, stage.min(CRACK_STAGES - 1))
+        })
+    }
+}
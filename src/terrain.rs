@@ -1,27 +1,37 @@
 use std::{collections::HashMap, sync::{Arc, RwLock}};
 
 use cgmath::{Matrix4, Vector2, Vector3};
-use image::ImageFormat;
-
-use crate::graphics::{
-    mesh::push_face,
-    resources::{GLRenderable, GLResources},
-    shader::Shader,
-    source::{TERRAIN_BITMAP, TERRAIN_FRAG_SRC, TERRAIN_VERT_SRC},
-    texture::Texture,
-    uniform::Uniform,
-    vertex::Vertex3D,
+
+use crate::{
+    graphics::{
+        atlas::AtlasRegion,
+        mesh::{push_box_face, push_face, push_face_rotated},
+        resources::{GLRenderable, GLResources},
+        shader::Shader,
+        source::{TERRAIN_FRAG_SRC, TERRAIN_TRANSLUCENT_FRAG_SRC, TERRAIN_VERT_SRC},
+        texture::{Texture, TextureQuality},
+        texture_pack::TexturePack,
+        uniform::Uniform,
+        vertex::Vertex3D,
+    },
+    physics::collision::Rect3,
 };
 
 use self::{
-    block::{MeshType, BLOCKS},
-    chunk::{Chunk, CHUNK_HEIGHT, CHUNK_WIDTH}, generation::TerrainGenConfig,
+    block::{MeshType, UseBehaviour, BLOCKS},
+    block_state::BlockState,
+    chunk::{Chunk, ChunkUpdate, CHUNK_HEIGHT, CHUNK_WIDTH}, generation::TerrainGenConfig,
+    model::block_model,
+    tick::BlockUpdateScheduler,
 };
 
 pub(crate) mod block;
+pub(crate) mod block_state;
 pub(crate) mod chunk;
 pub(crate) mod generation;
+pub(crate) mod model;
 mod save;
+mod tick;
 
 pub type BlockWorldPos = Vector3<isize>;
 pub type ChunkIndex = Vector2<isize>;
@@ -30,11 +40,33 @@ pub type BlockIndex = Vector3<usize>;
 pub enum TerrainEvent {
     LoadingZones(Vec<ChunkIndex>),
     ModifyBlock(BlockWorldPos, usize),
+    PlaceBlock(BlockWorldPos, usize, BlockState),
+}
+
+/// Outcome of the player using a block
+pub enum BlockUseResult {
+    /// The block has no use action, so the held block should be placed against it
+    Pass,
+    /// The block handled the interaction and placement is cancelled
+    Consumed,
+    /// The block's interface should be opened, cancelling placement
+    OpenInterface(BlockWorldPos),
 }
 
 const NUM_CHUNK_LISTS: usize = 2;
 type ChunkList = [HashMap<ChunkIndex, Arc<RwLock<Box<Chunk>>>>; NUM_CHUNK_LISTS];
 
+/// Chunks kept loaded around the player unless the settings say otherwise
+pub(crate) const DEFAULT_LOADING_RADIUS: isize = 6;
+
+/// A chunk's geometry split by how it is rendered
+pub(crate) struct ChunkMesh {
+    /// Drawn into the G-buffer, with cut-out texels discarded
+    pub opaque: Vec<Vertex3D>,
+    /// Blended over the composited scene, uploaded as `chunk_x_y_translucent`
+    pub translucent: Vec<Vertex3D>,
+}
+
 pub struct Terrain {
     /* Multi-level queue for chunk data
      * 0: chunks in view to be actively updated/drawn each tick/frame
@@ -46,8 +78,11 @@ pub struct Terrain {
     chunk_update_queue: Vec<ChunkIndex>,
 
     event_queue: Vec<TerrainEvent>,
+    block_updates: BlockUpdateScheduler,
 
     config: TerrainGenConfig,
+    /// Chunks kept loaded around each loading zone
+    loading_radius: isize,
 }
 
 trait ChunkListTrait {
@@ -99,11 +134,18 @@ impl Terrain {
             block_placement_queue: HashMap::new(),
             chunk_update_queue: Vec::new(),
             event_queue: Vec::new(),
+            block_updates: BlockUpdateScheduler::new(),
 
             config,
+            loading_radius: DEFAULT_LOADING_RADIUS,
         }
     }
 
+    pub fn set_loading_radius(&mut self, loading_radius: isize) {
+        assert!(loading_radius > 0);
+        self.loading_radius = loading_radius;
+    }
+
     pub fn event(&mut self, event: TerrainEvent) {
         self.event_queue.push(event);
     }
@@ -116,8 +158,7 @@ impl Terrain {
                     let [ref mut cur_visible, ref mut backburner] = self.chunks;
                     backburner.extend(cur_visible.drain());
 
-                    let radius = 6;
-                    assert!(radius > 0);
+                    let radius = self.loading_radius;
                     for chunk_index in active_chunks {
                         for x in -radius..=radius {
                             for z in -radius..=radius {
@@ -133,15 +174,15 @@ impl Terrain {
 
                 },
                 TerrainEvent::ModifyBlock(block_world_pos, new_value) => {
-                    if let Some((chunk_index, block_index)) = Self::chunk_and_block_index(&block_world_pos) {
-                        if let Some(chunk) = self.chunks.at_index_mut(&chunk_index) {
-                            let mut chunk = chunk.write().unwrap();
-                            chunk.set_block(&block_index, new_value);
-                        }
-                    }
+                    self.set_block_at_world_pos(&block_world_pos, new_value);
+                },
+                TerrainEvent::PlaceBlock(block_world_pos, new_value, state) => {
+                    self.set_block_with_state_at_world_pos(&block_world_pos, new_value, state);
                 },
             }
         }
+
+        self.tick_blocks();
     }
 
     /// Fetch the ID of the block at the global position `world_pos`
@@ -157,9 +198,48 @@ impl Terrain {
         }
     }
 
+    /// Fetch the state of the block at the global position `world_pos`
+    pub fn block_state_at_world_pos(&self, world_pos: &BlockWorldPos) -> BlockState {
+        if let Some((chunk_index, block_index)) = Terrain::chunk_and_block_index(world_pos) {
+            if let Some(chunk) = self.chunks.at_index(&chunk_index) {
+                let chunk = chunk.read().unwrap();
+                return chunk.get_block_state(&block_index);
+            }
+        }
+        BlockState::default()
+    }
+
+    /// Update the state of the block at `world_pos`, notifying neighbors if it changed
+    pub fn set_block_state_at_world_pos(&mut self, world_pos: &BlockWorldPos, state: BlockState) {
+        if let Some((chunk_index, block_index)) = Terrain::chunk_and_block_index(world_pos) {
+            if let Some(chunk) = self.chunks.at_index_mut(&chunk_index) {
+                let mut chunk = chunk.write().unwrap();
+                if chunk.get_block_state(&block_index) != state {
+                    chunk.set_block_state(&block_index, state);
+                    self.block_updates.push_update(ChunkUpdate::BlockUpdate(chunk_index, block_index));
+                }
+            }
+        }
+    }
+
+    /// Run the use action of the block at `world_pos`
+    pub fn use_block(&mut self, world_pos: &BlockWorldPos) -> BlockUseResult {
+        let block = &BLOCKS[self.block_at_world_pos(world_pos)];
+        match block.use_behaviour {
+            UseBehaviour::None => BlockUseResult::Pass,
+            UseBehaviour::Toggle => {
+                let mut state = self.block_state_at_world_pos(world_pos);
+                state.active = !state.active;
+                self.set_block_state_at_world_pos(world_pos, state);
+                BlockUseResult::Consumed
+            }
+            UseBehaviour::OpenInterface => BlockUseResult::OpenInterface(*world_pos),
+        }
+    }
+
     /// Convert from world coordinates to chunk indices, and the block index within the chunk
     pub fn chunk_and_block_index(world_pos: &BlockWorldPos) -> Option<(ChunkIndex, BlockIndex)> {
-        if world_pos.y > (CHUNK_HEIGHT - 1) as isize {
+        if world_pos.y < 0 || world_pos.y > (CHUNK_HEIGHT - 1) as isize {
             None
         } else {
             let chunk_index = ChunkIndex {
@@ -179,15 +259,20 @@ impl Terrain {
     pub(crate) fn generate_chunk_vertices(
         &self,
         chunk_index: &ChunkIndex,
-    ) -> Option<Vec<Vertex3D>> {
+        texture_pack: &TexturePack,
+    ) -> Option<ChunkMesh> {
         if let Some(chunk) = self.chunks.at_index(chunk_index) {
             let chunk = chunk.read().unwrap();
-            let x_pos_chunk = self.chunks.at_index(&(chunk_index + ChunkIndex::new(1, 0)));
-            let x_neg_chunk = self.chunks.at_index(&(chunk_index + ChunkIndex::new(-1, 0)));
-            let z_pos_chunk = self.chunks.at_index(&(chunk_index + ChunkIndex::new(0, 1)));
-            let z_neg_chunk = self.chunks.at_index(&(chunk_index + ChunkIndex::new(0, -1)));
-
-            let mut vertices = Vec::new();
+            let neighbor_chunks = [
+                self.chunks.at_index(&(chunk_index + ChunkIndex::new(1, 0))),  //x_pos
+                self.chunks.at_index(&(chunk_index + ChunkIndex::new(-1, 0))), //x_neg
+                self.chunks.at_index(&(chunk_index + ChunkIndex::new(0, 1))),  //z_pos
+                self.chunks.at_index(&(chunk_index + ChunkIndex::new(0, -1))), //z_neg
+            ];
+            let atlas = &texture_pack.atlas;
+
+            let mut opaque_vertices = Vec::new();
+            let mut translucent_vertices = Vec::new();
             for x in 0..CHUNK_WIDTH {
                 for y in 0..CHUNK_HEIGHT {
                     for z in 0..CHUNK_WIDTH {
@@ -197,239 +282,85 @@ impl Terrain {
                             continue;
                         }
                         let cur = &block::BLOCKS[i];
-                        let tex_coords: [(f32, f32); 6] =
-                            if let Some(texture_type) = &cur.texture_map {
-                                let mut coords = [(0.0f32, 0.0f32); 6];
-                                match texture_type {
-                                    block::TextureType::Single(x, y) => {
-                                        for item in &mut coords {
-                                            *item = (*x, *y);
-                                        }
-                                    }
-                                    block::TextureType::TopAndSide(
-                                        (x_top, y_top),
-                                        (x_side, y_side),
-                                    ) => {
-                                        coords[0] = (*x_side, *y_side);
-                                        coords[1] = (*x_side, *y_side);
-                                        coords[2] = (*x_top, *y_top);
-                                        coords[3] = (*x_side, *y_side);
-                                        coords[4] = (*x_side, *y_side);
-                                        coords[5] = (*x_side, *y_side);
-                                    }
-                                    block::TextureType::TopSideBottom(
-                                        (x_top, y_top),
-                                        (x_side, y_side),
-                                        (x_bottom, y_bottom),
-                                    ) => {
-                                        coords[0] = (*x_side, *y_side);
-                                        coords[1] = (*x_side, *y_side);
-                                        coords[2] = (*x_top, *y_top);
-                                        coords[3] = (*x_bottom, *y_bottom);
-                                        coords[4] = (*x_side, *y_side);
-                                        coords[5] = (*x_side, *y_side);
-                                    }
-                                    block::TextureType::TopSideFrontActivatable(
-                                        (x_front_inactive, y_front_inactive),
-                                        (x_front_active, y_front_active),
-                                        (x_side, y_side),
-                                        (x_top, y_top),
-                                    ) => {
-                                        coords[0] = (*x_side, *y_side);
-                                        coords[1] = (*x_side, *y_side);
-                                        coords[2] = (*x_top, *y_top);
-                                        coords[3] = (*x_top, *y_top);
-                                        coords[4] = (*x_side, *y_side);
-                                        //let active = chunk.metadata[x][y][z] == 1;
-                                        let active = chunk.get_metadata(&block_index) == 1;
-                                        coords[5] = if active {
-                                            (*x_front_active, *y_front_active)
-                                        } else {
-                                            (*x_front_inactive, *y_front_inactive)
-                                        };
-                                    }
-                                }
-                                coords
-                            } else {
-                                [(0.0, 0.0); 6]
-                            };
+                        let vertices = if cur.translucent {
+                            &mut translucent_vertices
+                        } else {
+                            &mut opaque_vertices
+                        };
+                        let tex_coords = match &cur.texture_map {
+                            Some(texture_type) => {
+                                let active = chunk.get_block_state(&block_index).active;
+                                atlas.face_regions(&texture_type.face_textures(active))
+                            }
+                            None => [AtlasRegion::default(); 6],
+                        };
+                        let (tex_coords, uv_rotations) = chunk
+                            .get_block_state(&block_index)
+                            .orient_faces(cur, tex_coords);
 
                         let position = [x as f32, y as f32, z as f32];
                         let vertex_type = cur.block_type as i32;
                         match cur.mesh_type {
                             MeshType::Block => {
-                                let (x_pos_block, x_pos_lighting) = if x < CHUNK_WIDTH - 1 {
-                                    let x_pos_index = BlockIndex::new(x + 1, y, z);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&x_pos_index)]),
-                                        chunk.get_lighting(&x_pos_index),
-                                    )
-                                } else {
-                                    let x_pos_index = BlockIndex::new(0, y, z);
-                                    x_pos_chunk
-                                        .map(|adjacent_chunk| {
-                                            let adjacent_chunk = adjacent_chunk.read().unwrap();
-                                            (
-                                                Some(
-                                                    BLOCKS[adjacent_chunk.get_block(&x_pos_index)],
-                                                ),
-                                                adjacent_chunk.get_lighting(&x_pos_index),
-                                            )
-                                        })
-                                        .unwrap_or((None, 0))
-                                };
-                                if let Some(adjacent_block) = x_pos_block {
-                                    if adjacent_block.transparent {
-                                        push_face(
-                                            &position,
-                                            0,
-                                            &mut vertices,
-                                            &tex_coords[0],
-                                            vertex_type,
-                                            x_pos_lighting as f32,
-                                        );
-                                    }
-                                }
-
-                                let (x_neg_block, x_neg_lighting) = if x > 0 {
-                                    let x_neg_index = Vector3::new(x - 1, y, z);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&x_neg_index)]),
-                                        chunk.get_lighting(&x_neg_index),
-                                    )
-                                } else {
-                                    let x_neg_index = Vector3::new(CHUNK_WIDTH - 1, y, z);
-                                    x_neg_chunk
-                                        .map(|adjacent_chunk| {
-                                            let adjacent_chunk = adjacent_chunk.read().unwrap();
-                                            (
-                                                Some(
-                                                    BLOCKS[adjacent_chunk.get_block(&x_neg_index)],
-                                                ),
-                                                adjacent_chunk.get_lighting(&x_neg_index),
-                                            )
-                                        })
-                                        .unwrap_or((None, 0))
-                                };
-                                if let Some(adjacent_block) = x_neg_block {
-                                    if adjacent_block.transparent {
-                                        push_face(
-                                            &position,
-                                            1,
-                                            &mut vertices,
-                                            &tex_coords[1],
-                                            vertex_type,
-                                            x_neg_lighting as f32,
-                                        );
-                                    }
-                                }
-
-                                let (y_pos_block, y_pos_lighting) = if y < CHUNK_HEIGHT - 1 {
-                                    let y_pos_index = Vector3::new(x, y + 1, z);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&y_pos_index)]),
-                                        chunk.get_lighting(&y_pos_index),
-                                    )
-                                } else {
-                                    (None, 0)
-                                };
-                                if let Some(adjacent_block) = y_pos_block {
-                                    if adjacent_block.transparent {
-                                        push_face(
-                                            &position,
-                                            2,
-                                            &mut vertices,
-                                            &tex_coords[2],
-                                            vertex_type,
-                                            y_pos_lighting as f32,
-                                        );
-                                    }
-                                }
-
-                                let (y_neg_block, y_neg_lighting) = if y > 0 {
-                                    let y_neg_index = Vector3::new(x, y - 1, z);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&y_neg_index)]),
-                                        chunk.get_lighting(&y_neg_index),
-                                    )
-                                } else {
-                                    (None, 0)
-                                };
-                                if let Some(adjacent_block) = y_neg_block {
-                                    if adjacent_block.transparent {
-                                        push_face(
-                                            &position,
-                                            3,
-                                            &mut vertices,
-                                            &tex_coords[3],
-                                            vertex_type,
-                                            y_neg_lighting as f32,
-                                        );
-                                    }
-                                }
-
-                                let (z_pos_block, z_pos_lighting) = if z < CHUNK_WIDTH - 1 {
-                                    let z_pos_index = Vector3::new(x, y, z + 1);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&z_pos_index)]),
-                                        chunk.get_lighting(&z_pos_index),
-                                    )
-                                } else {
-                                    let z_pos_index = Vector3::new(x, y, 0);
-                                    z_pos_chunk
-                                        .map(|adjacent_chunk| {
-                                            let adjacent_chunk = adjacent_chunk.read().unwrap();
-                                            (
-                                                Some(
-                                                    BLOCKS[adjacent_chunk.get_block(&z_pos_index)],
-                                                ),
-                                                adjacent_chunk.get_lighting(&z_pos_index),
-                                            )
-                                        })
-                                        .unwrap_or((None, 16))
-                                };
-                                if let Some(adjacent_block) = z_pos_block {
-                                    if adjacent_block.transparent {
-                                        push_face(
-                                            &position,
-                                            4,
-                                            &mut vertices,
-                                            &tex_coords[4],
-                                            vertex_type,
-                                            z_pos_lighting as f32,
-                                        );
+                                for face in 0..6 {
+                                    if let Some((adjacent_id, adjacent_state, adjacent_lighting)) =
+                                        adjacent_block(&chunk, &neighbor_chunks, &block_index, face)
+                                    {
+                                        // Faces between two of the same see-through block, like
+                                        // panes of glass side by side, can never be seen
+                                        if !(BLOCKS[adjacent_id].occludes_face(&adjacent_state, face ^ 1)
+                                            || cur.transparent && adjacent_id == i)
+                                        {
+                                            push_face_rotated(
+                                                &position,
+                                                face,
+                                                vertices,
+                                                &tex_coords[face],
+                                                uv_rotations[face],
+                                                vertex_type,
+                                                adjacent_lighting as f32,
+                                            );
+                                        }
                                     }
                                 }
-
-                                let (z_neg_index, z_neg_lighting) = if z > 0 {
-                                    let z_neg_index = Vector3::new(x, y, z - 1);
-                                    (
-                                        Some(BLOCKS[chunk.get_block(&z_neg_index)]),
-                                        chunk.get_lighting(&z_neg_index),
-                                    )
-                                } else {
-                                    let z_neg_index = Vector3::new(x, y, CHUNK_WIDTH - 1);
-                                    z_neg_chunk
-                                        .map(|adjacent_chunk| {
-                                            let adjacent_chunk = adjacent_chunk.read().unwrap();
-                                            (
-                                                Some(
-                                                    BLOCKS[adjacent_chunk.get_block(&z_neg_index)],
-                                                ),
-                                                adjacent_chunk.get_lighting(&z_neg_index),
-                                            )
-                                        })
-                                        .unwrap_or((None, 0))
-                                };
-                                if let Some(adjacent_block) = z_neg_index {
-                                    if adjacent_block.transparent {
-                                        push_face(
+                            }
+                            MeshType::Model(name) => {
+                                let lighting = chunk.get_lighting(&block_index) as f32;
+                                let state = chunk.get_block_state(&block_index);
+                                for model_box in block_model(name).boxes(cur, &state) {
+                                    for (face, face_coords) in tex_coords.iter().enumerate() {
+                                        let uv = match model_box.face_uv(face) {
+                                            Some(uv) => uv,
+                                            None => continue,
+                                        };
+                                        // Faces on the block boundary are hidden like a cube's would be
+                                        if model_box.touches_face(face) {
+                                            let adjacent = adjacent_block(
+                                                &chunk,
+                                                &neighbor_chunks,
+                                                &block_index,
+                                                face,
+                                            );
+                                            let hidden = match adjacent {
+                                                Some((adjacent_id, adjacent_state, _)) => BLOCKS
+                                                    [adjacent_id]
+                                                    .occludes_face(&adjacent_state, face ^ 1),
+                                                None => true,
+                                            };
+                                            if hidden {
+                                                continue;
+                                            }
+                                        }
+                                        push_box_face(
                                             &position,
-                                            5,
-                                            &mut vertices,
-                                            &tex_coords[5],
+                                            face,
+                                            &model_box,
+                                            &uv,
+                                            vertices,
+                                            face_coords,
+                                            0,
                                             vertex_type,
-                                            z_neg_lighting as f32,
+                                            lighting,
                                         );
                                     }
                                 }
@@ -439,7 +370,7 @@ impl Terrain {
                                 push_face(
                                     &position,
                                     6,
-                                    &mut vertices,
+                                    vertices,
                                     &tex_coords[0],
                                     vertex_type,
                                     lighting,
@@ -447,7 +378,7 @@ impl Terrain {
                                 push_face(
                                     &position,
                                     7,
-                                    &mut vertices,
+                                    vertices,
                                     &tex_coords[0],
                                     vertex_type,
                                     lighting,
@@ -455,7 +386,7 @@ impl Terrain {
                                 push_face(
                                     &position,
                                     8,
-                                    &mut vertices,
+                                    vertices,
                                     &tex_coords[0],
                                     vertex_type,
                                     lighting,
@@ -463,7 +394,7 @@ impl Terrain {
                                 push_face(
                                     &position,
                                     9,
-                                    &mut vertices,
+                                    vertices,
                                     &tex_coords[0],
                                     vertex_type,
                                     lighting,
@@ -474,16 +405,39 @@ impl Terrain {
                 }
             }
 
-            if vertices.is_empty() {
+            if opaque_vertices.is_empty() && translucent_vertices.is_empty() {
                 None
             } else {
-                Some(vertices)
+                Some(ChunkMesh {
+                    opaque: opaque_vertices,
+                    translucent: translucent_vertices,
+                })
             }
         } else {
             None
         }
     }
 
+    /// Boxes used to pick the block at `world_pos` with a raycast, in world coordinates
+    pub fn selection_boxes_at_world_pos(&self, world_pos: &BlockWorldPos) -> Vec<Rect3> {
+        let state = self.block_state_at_world_pos(world_pos);
+        BLOCKS[self.block_at_world_pos(world_pos)]
+            .selection_boxes(&state)
+            .iter()
+            .map(|model_box| model_box.world_rect(world_pos))
+            .collect()
+    }
+
+    /// Boxes which entities collide with at `world_pos`, in world coordinates
+    pub fn collision_boxes_at_world_pos(&self, world_pos: &BlockWorldPos) -> Vec<Rect3> {
+        let state = self.block_state_at_world_pos(world_pos);
+        BLOCKS[self.block_at_world_pos(world_pos)]
+            .collision_boxes(&state)
+            .iter()
+            .map(|model_box| model_box.world_rect(world_pos))
+            .collect()
+    }
+
     pub fn collision_at_world_pos(&self, world_pos: &BlockWorldPos) -> bool {
         if let Some((chunk_index, block_index)) = Terrain::chunk_and_block_index(world_pos) {
             if let Some(chunk) = self.chunks.at_index(&chunk_index) {
@@ -501,12 +455,18 @@ impl Terrain {
         &mut self,
         chunk_index: &ChunkIndex,
         gl_resources: &mut GLResources,
+        texture_pack: &TexturePack,
     ) {
         if self.chunks.at_index(chunk_index).is_some() {
-            if let Some(chunk_vertices) = self.generate_chunk_vertices(chunk_index) {
+            if let Some(chunk_mesh) = self.generate_chunk_vertices(chunk_index, texture_pack) {
                 let name = format!("chunk_{}_{}", chunk_index.x, chunk_index.y);
-                let verts = Box::new(chunk_vertices);
-                gl_resources.update_vao_buffer(name, verts);
+                // Most chunks have nothing translucent, so don't keep an empty buffer to draw for them
+                if chunk_mesh.translucent.is_empty() {
+                    gl_resources.remove_vao_buffer(format!("{}_translucent", name));
+                } else {
+                    gl_resources.update_vao_buffer(format!("{}_translucent", name), Box::new(chunk_mesh.translucent));
+                }
+                gl_resources.update_vao_buffer(name, Box::new(chunk_mesh.opaque));
                 if let Some(chunk) = self.chunks.at_index_mut(chunk_index) {
                     let mut chunk = chunk.write().unwrap();
                     chunk.needs_mesh_rebuild = false;
@@ -519,6 +479,7 @@ impl Terrain {
         &mut self,
         chunk_index: &ChunkIndex,
         gl_resources: &mut GLResources,
+        texture_pack: &TexturePack,
     ) {
         let adjacent_chunks = [
             chunk_index + ChunkIndex::new(1, 0),  //x_pos
@@ -526,9 +487,9 @@ impl Terrain {
             chunk_index + ChunkIndex::new(0, 1),  //z_pos
             chunk_index + ChunkIndex::new(0, -1), //z_neg
         ];
-        self.update_single_chunk_mesh(chunk_index, gl_resources);
+        self.update_single_chunk_mesh(chunk_index, gl_resources, texture_pack);
         for chunk_index in adjacent_chunks {
-            self.update_single_chunk_mesh(&chunk_index, gl_resources);
+            self.update_single_chunk_mesh(&chunk_index, gl_resources, texture_pack);
         }
     }
 
@@ -541,7 +502,27 @@ impl Terrain {
         BLOCKS[self.block_at_world_pos(world_pos)].solid
     }
 
-    pub fn update_meshes(&mut self, gl_resources: &mut GLResources) {
+    /// Whether the chunk holding the column of blocks at `world_pos` is loaded, at any height
+    pub fn column_loaded_at_world_pos(&self, world_pos: &BlockWorldPos) -> bool {
+        let chunk_index = ChunkIndex::new(
+            world_pos.x.div_euclid(CHUNK_WIDTH as isize),
+            world_pos.z.div_euclid(CHUNK_WIDTH as isize),
+        );
+        self.chunks.at_index(&chunk_index).is_some()
+    }
+
+    /// Whether a block placed at `world_pos` would be kept, which needs it inside the world's height,
+    /// in a loaded chunk and not in a solid block
+    pub fn can_place_at_world_pos(&self, world_pos: &BlockWorldPos) -> bool {
+        match Terrain::chunk_and_block_index(world_pos) {
+            Some((chunk_index, _)) => {
+                self.chunks.at_index(&chunk_index).is_some() && !self.solid_block_at_world_pos(world_pos)
+            }
+            None => false,
+        }
+    }
+
+    pub fn update_meshes(&mut self, gl_resources: &mut GLResources, texture_pack: &TexturePack) {
         let mut rebuild = Vec::new();
         for (index, chunk) in self.chunks[0].iter_mut() {
             let chunk = chunk.read().unwrap();
@@ -551,7 +532,14 @@ impl Terrain {
         }
 
         for index in rebuild {
-            self.update_chunk_mesh(&index, gl_resources);
+            self.update_chunk_mesh(&index, gl_resources, texture_pack);
+        }
+    }
+
+    /// Have every loaded chunk's mesh rebuilt, for when the textures it was built with change
+    pub(crate) fn rebuild_all_meshes(&self) {
+        for chunk in self.chunks[0].values() {
+            chunk.write().unwrap().needs_mesh_rebuild = true;
         }
     }
 
@@ -564,31 +552,147 @@ impl Terrain {
     pub fn terrain_config(&self) -> &TerrainGenConfig {
         &self.config
     }
+
+    /// Chunks within render distance, which are drawn every frame
+    pub fn visible_chunk_count(&self) -> usize {
+        self.chunks[0].len()
+    }
+
+    /// Every chunk held in memory, whether it's drawn or not
+    pub fn loaded_chunk_count(&self) -> usize {
+        self.chunks.iter().map(HashMap::len).sum()
+    }
+}
+
+/// Block ID, state and lighting of the block next to `block_index` across `face`, or `None`
+/// if it's outside the world or in a chunk which isn't loaded.
+/// `neighbor_chunks` are the adjacent chunks in +X, -X, +Z, -Z order
+fn adjacent_block(
+    chunk: &Chunk,
+    neighbor_chunks: &[Option<&Arc<RwLock<Box<Chunk>>>>; 4],
+    block_index: &BlockIndex,
+    face: usize,
+) -> Option<(usize, BlockState, usize)> {
+    let (x, y, z) = (block_index.x, block_index.y, block_index.z);
+    let (neighbor, adjacent_index) = match face {
+        0 if x == CHUNK_WIDTH - 1 => (neighbor_chunks[0], BlockIndex::new(0, y, z)),
+        1 if x == 0 => (neighbor_chunks[1], BlockIndex::new(CHUNK_WIDTH - 1, y, z)),
+        4 if z == CHUNK_WIDTH - 1 => (neighbor_chunks[2], BlockIndex::new(x, y, 0)),
+        5 if z == 0 => (neighbor_chunks[3], BlockIndex::new(x, y, CHUNK_WIDTH - 1)),
+        2 if y == CHUNK_HEIGHT - 1 => return None,
+        3 if y == 0 => return None,
+        _ => {
+            let adjacent_index = match face {
+                0 => BlockIndex::new(x + 1, y, z),
+                1 => BlockIndex::new(x - 1, y, z),
+                2 => BlockIndex::new(x, y + 1, z),
+                3 => BlockIndex::new(x, y - 1, z),
+                4 => BlockIndex::new(x, y, z + 1),
+                _ => BlockIndex::new(x, y, z - 1),
+            };
+            return Some((
+                chunk.get_block(&adjacent_index),
+                chunk.get_block_state(&adjacent_index),
+                chunk.get_lighting(&adjacent_index),
+            ));
+        }
+    };
+    neighbor.map(|adjacent_chunk| {
+        let adjacent_chunk = adjacent_chunk.read().unwrap();
+        (
+            adjacent_chunk.get_block(&adjacent_index),
+            adjacent_chunk.get_block_state(&adjacent_index),
+            adjacent_chunk.get_lighting(&adjacent_index),
+        )
+    })
 }
 
 impl GLRenderable for Terrain {
-    fn init_gl_resources(&self, gl_resources: &mut GLResources) {
-        // Texture is also used by drops and may already exist
+    fn init_gl_resources(&self, gl_resources: &mut GLResources, texture_pack: &TexturePack) {
+        // Texture is also used by drops, and is normally uploaded beforehand with the configured quality
         if gl_resources.get_texture("terrain").is_none() {
             let terrain_texture =
-                Texture::from_dynamic_image_bytes(TERRAIN_BITMAP, ImageFormat::Png);
+                Texture::from_mip_levels(texture_pack.atlas.levels(), TextureQuality::default());
             gl_resources.add_texture("terrain", terrain_texture);
         }
 
         let terrain_program = Shader::new(TERRAIN_VERT_SRC, TERRAIN_FRAG_SRC).unwrap();
         gl_resources.add_shader("terrain", terrain_program);
+        let translucent_program = Shader::new(TERRAIN_VERT_SRC, TERRAIN_TRANSLUCENT_FRAG_SRC).unwrap();
+        gl_resources.add_shader("terrain_translucent", translucent_program);
 
         for chunk_index in self.chunks[0].keys() {
-            if let Some(chunk_vertices) = self.generate_chunk_vertices(chunk_index) {
+            if let Some(chunk_mesh) = self.generate_chunk_vertices(chunk_index, texture_pack) {
                 let name = format!("chunk_{}_{}", chunk_index.x, chunk_index.y);
-                let verts = Box::new(chunk_vertices);
-                gl_resources.create_or_update_vao(name, verts);
+                if chunk_mesh.translucent.is_empty() {
+                    gl_resources.remove_vao(&format!("{}_translucent", name));
+                } else {
+                    gl_resources.create_or_update_vao(format!("{}_translucent", name), Box::new(chunk_mesh.translucent));
+                }
+                gl_resources.create_or_update_vao(name, Box::new(chunk_mesh.opaque));
             }
         }
     }
 
     fn draw(&self, gl_resources: &GLResources, uniforms: &[(&str, Box<dyn Uniform>)]) {
-        let shader = gl_resources.get_shader("terrain").unwrap();
+        self.draw_chunks(gl_resources, "terrain", uniforms);
+    }
+
+    fn draw_shadow(&self, gl_resources: &GLResources, uniforms: &[(&str, Box<dyn Uniform>)]) {
+        self.draw_chunks(gl_resources, "shadow", uniforms);
+    }
+}
+
+impl Terrain {
+    /// Blend the translucent parts of every chunk over what's already drawn, furthest chunk first so
+    /// nearer glass is drawn over further glass
+    pub(crate) fn draw_translucent(
+        &self,
+        gl_resources: &GLResources,
+        uniforms: &[(&str, Box<dyn Uniform>)],
+        camera_position: Vector3<f32>,
+    ) {
+        let shader = gl_resources.get_shader("terrain_translucent").unwrap();
+        let texture = gl_resources.get_texture("terrain").unwrap();
+
+        texture.use_as_framebuffer_texture(0);
+        shader.use_program();
+        for (name, uniform) in uniforms {
+            uniform.set_as_uniform(shader, name);
+        }
+        shader.set_texture(unsafe { c_str!("texture_map") }, 0);
+
+        let chunk_distance = |chunk_index: &ChunkIndex| {
+            let center_x = (chunk_index.x as f32 + 0.5) * CHUNK_WIDTH as f32;
+            let center_z = (chunk_index.y as f32 + 0.5) * CHUNK_WIDTH as f32;
+            (center_x - camera_position.x).powi(2) + (center_z - camera_position.z).powi(2)
+        };
+        let mut chunk_indices: Vec<&ChunkIndex> = self.chunks[0].keys().collect();
+        chunk_indices.sort_by(|a, b| chunk_distance(b).total_cmp(&chunk_distance(a)));
+
+        unsafe {
+            gl::DepthMask(gl::FALSE);
+        }
+        for chunk_index in chunk_indices {
+            let name = format!("chunk_{}_{}_translucent", chunk_index.x, chunk_index.y);
+            if let Some(vao) = gl_resources.get_vao(&name) {
+                let model_matrix = Matrix4::from_translation(Vector3::new(
+                    (chunk_index.x * CHUNK_WIDTH as isize) as f32,
+                    0f32,
+                    (chunk_index.y * CHUNK_WIDTH as isize) as f32,
+                ));
+                shader.set_mat4(unsafe { c_str!("model_matrix") }, &model_matrix);
+                vao.draw();
+            }
+        }
+        unsafe {
+            gl::DepthMask(gl::TRUE);
+        }
+    }
+
+    /// Draw every chunk with the shader registered as `shader_name`
+    fn draw_chunks(&self, gl_resources: &GLResources, shader_name: &str, uniforms: &[(&str, Box<dyn Uniform>)]) {
+        let shader = gl_resources.get_shader(shader_name).unwrap();
         let texture = gl_resources.get_texture("terrain").unwrap();
 
         texture.use_as_framebuffer_texture(0);
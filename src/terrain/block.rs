@@ -1,11 +1,22 @@
-pub const BLOCKS: [Block; 14] = [
+use super::{
+    block_state::BlockState,
+    model::{block_model, ModelBox},
+};
+
+pub const BLOCKS: [Block; 22] = [
     Block {
         id: 0,
         name: "Air",
         solid: false,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
         texture_map: None,
     },
     Block {
@@ -13,130 +24,327 @@ pub const BLOCKS: [Block; 14] = [
         name: "Stone",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(1.0, 15.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 1.5,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("stone")),
     },
     Block {
         id: 2,
         name: "Grass",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::TopSideBottom(
-            (0.0, 15.0),
-            (3.0, 15.0),
-            (2.0, 15.0),
-        )),
+        tick_behaviour: TickBehaviour::Grass,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.6,
+        tool: ToolType::Shovel,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::TopSideBottom("grass_top", "grass_side", "dirt")),
     },
     Block {
         id: 3,
         name: "Dirt",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(2.0, 15.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.5,
+        tool: ToolType::Shovel,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("dirt")),
     },
     Block {
         id: 4,
         name: "Rose",
         solid: false,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Grass,
         mesh_type: MeshType::CrossedPlanes,
-        texture_map: Some(TextureType::Single(12.0, 15.0)),
+        tick_behaviour: TickBehaviour::Plant,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("rose")),
     },
     Block {
         id: 5,
         name: "Oak Log",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::TopSideBottom(
-            (5.0, 14.0),
-            (4.0, 14.0),
-            (5.0, 14.0),
-        )),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Axis,
+        hardness: 2.0,
+        tool: ToolType::Axe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::TopSideBottom("oak_log_top", "oak_log_side", "oak_log_top")),
     },
     Block {
         id: 6,
         name: "Dandelion",
         solid: false,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Grass,
         mesh_type: MeshType::CrossedPlanes,
-        texture_map: Some(TextureType::Single(13.0, 15.0)),
+        tick_behaviour: TickBehaviour::Plant,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("dandelion")),
     },
     Block {
         id: 7,
         name: "Oak Leaves",
         solid: true,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Leaves,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(4.0, 12.0)),
+        tick_behaviour: TickBehaviour::Leaves,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.2,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("oak_leaves")),
     },
     Block {
         id: 8,
         name: "Short Grass",
         solid: false,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Grass,
         mesh_type: MeshType::CrossedPlanes,
-        texture_map: Some(TextureType::Single(7.0, 13.0)),
+        tick_behaviour: TickBehaviour::Plant,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("short_grass")),
     },
     Block {
         id: 9,
         name: "Fern",
         solid: false,
         transparent: true,
+        translucent: false,
         block_type: BlockType::Grass,
         mesh_type: MeshType::CrossedPlanes,
-        texture_map: Some(TextureType::Single(8.0, 12.0)),
+        tick_behaviour: TickBehaviour::Plant,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("fern")),
     },
     Block {
         id: 10,
         name: "Iron Ore",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(1.0, 13.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 3.0,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("iron_ore")),
     },
     Block {
         id: 11,
         name: "Coal",
         solid: true,
         transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(2.0, 13.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 3.0,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("coal_ore")),
     },
     Block {
         id: 12,
         name: "Glass",
         solid: true,
         transparent: true,
+        translucent: true,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(1.0, 12.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.3,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("glass")),
     },
     Block {
         id: 13,
         name: "Sand",
         solid: true,
         transparent: false,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Block,
+        tick_behaviour: TickBehaviour::Falling,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.5,
+        tool: ToolType::Shovel,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("sand")),
+    },
+    Block {
+        id: 14,
+        name: "Gravel",
+        solid: true,
+        transparent: false,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Block,
+        tick_behaviour: TickBehaviour::Falling,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.6,
+        tool: ToolType::Shovel,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("gravel")),
+    },
+    Block {
+        id: 15,
+        name: "Stone Slab",
+        solid: true,
+        transparent: true,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Model("slab"),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 2.0,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::TopSideBottom(
+            "stone_slab_top",
+            "stone_slab_side",
+            "stone_slab_top",
+        )),
+    },
+    Block {
+        id: 16,
+        name: "Stone Stairs",
+        solid: true,
+        transparent: true,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Model("stairs"),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Facing,
+        hardness: 1.5,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("stone")),
+    },
+    Block {
+        id: 17,
+        name: "Oak Fence",
+        solid: true,
+        transparent: true,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Model("fence_post"),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 2.0,
+        tool: ToolType::Axe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("oak_planks")),
+    },
+    Block {
+        id: 18,
+        name: "Torch",
+        solid: false,
+        transparent: true,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Model("torch"),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 0.0,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("torch")),
+    },
+    Block {
+        id: 19,
+        name: "Furnace",
+        solid: true,
+        transparent: false,
+        translucent: false,
         block_type: BlockType::Block,
         mesh_type: MeshType::Block,
-        texture_map: Some(TextureType::Single(2.0, 14.0)),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Facing,
+        hardness: 3.5,
+        tool: ToolType::Pickaxe,
+        use_behaviour: UseBehaviour::OpenInterface,
+        texture_map: Some(TextureType::TopSideFrontActivatable(
+            "furnace_front",
+            "furnace_front_lit",
+            "furnace_side",
+            "furnace_top",
+        )),
+    },
+    Block {
+        id: 20,
+        name: "Lever",
+        solid: false,
+        transparent: true,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Model("lever"),
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Facing,
+        hardness: 0.5,
+        tool: ToolType::None,
+        use_behaviour: UseBehaviour::Toggle,
+        texture_map: Some(TextureType::Single("cobblestone")),
+    },
+    Block {
+        id: 21,
+        name: "Oak Plank",
+        solid: true,
+        transparent: false,
+        translucent: false,
+        block_type: BlockType::Block,
+        mesh_type: MeshType::Block,
+        tick_behaviour: TickBehaviour::None,
+        orientation: BlockOrientation::Fixed,
+        hardness: 2.0,
+        tool: ToolType::Axe,
+        use_behaviour: UseBehaviour::None,
+        texture_map: Some(TextureType::Single("oak_planks")),
     },
-    //Block {id: 14, name: "Diamond Ore", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::Single(2.0, 12.0))},
-    //Block {id: 4, name: "Cobblestone", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::Single(0.0, 14.0)) },
-    //Block {id: 5, name: "Oak Plank", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::Single(4.0, 15.0)) },
-    //Block {id: 17, name: "Furnace", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::TopSideFrontActivatable((12.0, 13.0),(13.0, 12.0), (13.0, 13.0),(14.0, 12.0)))},
+    //Block {id: 14, name: "Diamond Ore", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::Single("diamond_ore"))},
+    //Block {id: 4, name: "Cobblestone", solid: true, transparent: false, block_type: BlockType::Block, mesh_type: MeshType::Block, texture_map: Some(TextureType::Single("cobblestone")) },
 ];
 
 pub fn block_index_by_name(name: &str) -> usize {
@@ -159,15 +367,80 @@ pub enum BlockType {
 pub enum MeshType {
     Block,
     CrossedPlanes,
+    /// Named box model from the block model definitions
+    Model(&'static str),
+}
+
+/// How a block reacts to random ticks, scheduled ticks and neighbor changes
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TickBehaviour {
+    None,
+    Grass,
+    Plant,
+    Leaves,
+    Falling,
+}
+
+/// Which part of the block state decides how the block is rotated when meshed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BlockOrientation {
+    Fixed,
+    Axis,
+    Facing,
+}
+
+/// Kind of tool which breaks a block faster than by hand
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ToolType {
+    None,
+    Pickaxe,
+    Axe,
+    Shovel,
 }
 
+/// What happens when the player uses a block, before falling back to placing the held block
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum UseBehaviour {
+    None,
+    /// Flip the block's `active` state
+    Toggle,
+    /// Open the block's interface
+    OpenInterface,
+}
+
+/// Names of the atlas textures a block is drawn with
 #[allow(unused)]
 #[derive(Clone, Copy)]
 pub enum TextureType {
-    Single(f32, f32),
-    TopAndSide((f32, f32), (f32, f32)),
-    TopSideBottom((f32, f32), (f32, f32), (f32, f32)),
-    TopSideFrontActivatable((f32, f32), (f32, f32), (f32, f32), (f32, f32)),
+    Single(&'static str),
+    TopAndSide(&'static str, &'static str),
+    TopSideBottom(&'static str, &'static str, &'static str),
+    /// Front, front when active, side and top
+    TopSideFrontActivatable(&'static str, &'static str, &'static str, &'static str),
+}
+
+impl TextureType {
+    /// Texture shown in the hotbar
+    pub fn icon(&self) -> &'static str {
+        match *self {
+            TextureType::Single(name) => name,
+            TextureType::TopAndSide(_, side) | TextureType::TopSideBottom(_, side, _) => side,
+            TextureType::TopSideFrontActivatable(front, _, _, _) => front,
+        }
+    }
+
+    /// Texture of each face in the block's default orientation, facing north
+    pub fn face_textures(&self, active: bool) -> [&'static str; 6] {
+        match *self {
+            TextureType::Single(name) => [name; 6],
+            TextureType::TopAndSide(top, side) => [side, side, top, side, side, side],
+            TextureType::TopSideBottom(top, side, bottom) => [side, side, top, bottom, side, side],
+            TextureType::TopSideFrontActivatable(front, front_active, side, top) => {
+                let front = if active { front_active } else { front };
+                [side, side, top, top, side, front]
+            }
+        }
+    }
 }
 
 #[allow(unused)]
@@ -176,9 +449,17 @@ pub struct Block {
     pub id: usize,
     pub name: &'static str,
     pub transparent: bool,
+    /// Blended over the scene in a separate pass instead of being drawn into the G-buffer
+    pub translucent: bool,
     pub solid: bool,
     pub block_type: BlockType,
     pub mesh_type: MeshType,
+    pub tick_behaviour: TickBehaviour,
+    pub orientation: BlockOrientation,
+    /// Resistance to being broken, 0 breaks instantly
+    pub hardness: f32,
+    pub tool: ToolType,
+    pub use_behaviour: UseBehaviour,
     pub texture_map: Option<TextureType>,
 }
 
@@ -188,8 +469,14 @@ impl Block {
         name: &'static str,
         solid: bool,
         transparent: bool,
+        translucent: bool,
         block_type: BlockType,
         mesh_type: MeshType,
+        tick_behaviour: TickBehaviour,
+        orientation: BlockOrientation,
+        hardness: f32,
+        tool: ToolType,
+        use_behaviour: UseBehaviour,
         texture_map: Option<TextureType>,
     ) -> Self {
         Self {
@@ -197,11 +484,48 @@ impl Block {
             name,
             solid,
             transparent,
+            translucent,
             block_type,
             mesh_type,
+            tick_behaviour,
+            orientation,
+            hardness,
+            tool,
+            use_behaviour,
             texture_map,
         }
     }
+
+    /// Whether this block completely hides the neighboring face which touches its `face`
+    pub fn occludes_face(&self, state: &BlockState, face: usize) -> bool {
+        match self.mesh_type {
+            MeshType::Block => !self.transparent,
+            MeshType::CrossedPlanes => false,
+            MeshType::Model(name) => block_model(name)
+                .boxes(self, state)
+                .iter()
+                .any(|model_box| model_box.covers_face(face)),
+        }
+    }
+
+    /// Boxes used for picking the block with a raycast
+    pub fn selection_boxes(&self, state: &BlockState) -> Vec<ModelBox> {
+        match self.mesh_type {
+            MeshType::Model(name) => block_model(name).boxes(self, state),
+            _ => vec![ModelBox::FULL],
+        }
+    }
+
+    /// Boxes which entities collide with, empty for blocks which aren't solid
+    pub fn collision_boxes(&self, state: &BlockState) -> Vec<ModelBox> {
+        if !self.solid {
+            return Vec::new();
+        }
+        match self.mesh_type {
+            MeshType::Model(name) => block_model(name).collision_boxes(self, state),
+            _ => vec![ModelBox::FULL],
+        }
+    }
 }
 
 impl Default for Block {
@@ -211,8 +535,14 @@ impl Default for Block {
             name: "Air",
             solid: false,
             transparent: true,
+            translucent: false,
             block_type: BlockType::Block,
             mesh_type: MeshType::Block,
+            tick_behaviour: TickBehaviour::None,
+            orientation: BlockOrientation::Fixed,
+            hardness: 0.0,
+            tool: ToolType::None,
+            use_behaviour: UseBehaviour::None,
             texture_map: None,
         }
     }
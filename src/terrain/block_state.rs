@@ -0,0 +1,269 @@
+use cgmath::Vector3;
+
+use super::{
+    block::{Block, BlockOrientation, TextureType},
+    BlockWorldPos,
+};
+
+/* Block state is packed into the chunk metadata array:
+ * bit 0:     active
+ * bits 1-3:  facing
+ * bits 4-5:  axis
+ * bits 6-9:  level
+ */
+const ACTIVE_SHIFT: usize = 0;
+const FACING_SHIFT: usize = 1;
+const AXIS_SHIFT: usize = 4;
+const LEVEL_SHIFT: usize = 6;
+
+const FACING_MASK: usize = 0b111;
+const AXIS_MASK: usize = 0b11;
+const LEVEL_MASK: usize = 0b1111;
+
+pub const MAX_LEVEL: usize = LEVEL_MASK;
+
+/// Horizontal faces in clockwise order when looking down the Y axis
+pub(crate) const HORIZONTAL_FACES: [usize; 4] = [5, 0, 4, 1];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Facing {
+    North,
+    South,
+    East,
+    West,
+    Up,
+    Down,
+}
+
+impl Facing {
+    const ALL: [Facing; 6] = [
+        Facing::North,
+        Facing::South,
+        Facing::East,
+        Facing::West,
+        Facing::Up,
+        Facing::Down,
+    ];
+
+    /// Index of the cube face pointing in this direction, matching the order used by the mesher
+    pub fn face_index(&self) -> usize {
+        match self {
+            Facing::East => 0,
+            Facing::West => 1,
+            Facing::Up => 2,
+            Facing::Down => 3,
+            Facing::South => 4,
+            Facing::North => 5,
+        }
+    }
+
+    pub fn normal(&self) -> BlockWorldPos {
+        match self {
+            Facing::East => BlockWorldPos::new(1, 0, 0),
+            Facing::West => BlockWorldPos::new(-1, 0, 0),
+            Facing::Up => BlockWorldPos::new(0, 1, 0),
+            Facing::Down => BlockWorldPos::new(0, -1, 0),
+            Facing::South => BlockWorldPos::new(0, 0, 1),
+            Facing::North => BlockWorldPos::new(0, 0, -1),
+        }
+    }
+
+    pub fn from_normal(normal: &BlockWorldPos) -> Option<Self> {
+        Facing::ALL.into_iter().find(|facing| facing.normal() == *normal)
+    }
+
+    /// The horizontal direction pointing back towards a viewer looking along `direction`
+    pub fn towards_viewer(direction: &Vector3<f32>) -> Self {
+        if direction.x.abs() > direction.z.abs() {
+            if direction.x > 0.0 {
+                Facing::West
+            } else {
+                Facing::East
+            }
+        } else if direction.z > 0.0 {
+            Facing::North
+        } else {
+            Facing::South
+        }
+    }
+
+    /// Clockwise quarter turns from north to this direction, `None` for up and down
+    pub fn horizontal_turns(&self) -> Option<usize> {
+        HORIZONTAL_FACES
+            .iter()
+            .position(|face| *face == self.face_index())
+    }
+
+    pub fn axis(&self) -> Axis {
+        match self {
+            Facing::East | Facing::West => Axis::X,
+            Facing::Up | Facing::Down => Axis::Y,
+            Facing::North | Facing::South => Axis::Z,
+        }
+    }
+
+    fn from_bits(bits: usize) -> Self {
+        Facing::ALL[bits % Facing::ALL.len()]
+    }
+
+    fn to_bits(self) -> usize {
+        Facing::ALL.iter().position(|facing| *facing == self).unwrap()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Axis {
+    Y,
+    X,
+    Z,
+}
+
+impl Axis {
+    fn from_bits(bits: usize) -> Self {
+        match bits {
+            1 => Axis::X,
+            2 => Axis::Z,
+            _ => Axis::Y,
+        }
+    }
+
+    fn to_bits(self) -> usize {
+        match self {
+            Axis::Y => 0,
+            Axis::X => 1,
+            Axis::Z => 2,
+        }
+    }
+}
+
+/// Typed view of the per-block metadata stored in a chunk
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BlockState {
+    pub facing: Facing,
+    pub axis: Axis,
+    pub active: bool,
+    pub level: usize,
+}
+
+impl Default for BlockState {
+    fn default() -> Self {
+        Self::from_metadata(0)
+    }
+}
+
+impl BlockState {
+    pub fn from_metadata(metadata: usize) -> Self {
+        Self {
+            facing: Facing::from_bits((metadata >> FACING_SHIFT) & FACING_MASK),
+            axis: Axis::from_bits((metadata >> AXIS_SHIFT) & AXIS_MASK),
+            active: (metadata >> ACTIVE_SHIFT) & 1 == 1,
+            level: (metadata >> LEVEL_SHIFT) & LEVEL_MASK,
+        }
+    }
+
+    pub fn to_metadata(self) -> usize {
+        (self.active as usize) << ACTIVE_SHIFT
+            | self.facing.to_bits() << FACING_SHIFT
+            | self.axis.to_bits() << AXIS_SHIFT
+            | self.level.min(MAX_LEVEL) << LEVEL_SHIFT
+    }
+
+    /// State for `block` placed against the face with normal `hit_normal` by a player looking along `look_direction`
+    pub fn for_placement(
+        block: &Block,
+        hit_normal: &BlockWorldPos,
+        look_direction: &Vector3<f32>,
+    ) -> Self {
+        let mut state = Self::default();
+        match block.orientation {
+            BlockOrientation::Fixed => {}
+            BlockOrientation::Axis => {
+                if let Some(facing) = Facing::from_normal(hit_normal) {
+                    state.axis = facing.axis();
+                }
+            }
+            BlockOrientation::Facing => {
+                state.facing = Facing::towards_viewer(look_direction);
+            }
+        }
+        state
+    }
+
+    /// Remap per-face textures from the block's default orientation (facing north, Y axis) to this
+    /// state. Also returns the number of quarter turns to rotate each face's UVs by.
+    pub fn orient_faces<T: Copy>(&self, block: &Block, coords: [T; 6]) -> ([T; 6], [usize; 6]) {
+        match (block.orientation, block.texture_map) {
+            (BlockOrientation::Axis, _) => match self.axis {
+                Axis::Y => (coords, [0; 6]),
+                Axis::X => (
+                    [coords[2], coords[3], coords[0], coords[1], coords[4], coords[5]],
+                    [0, 0, 1, 1, 1, 1],
+                ),
+                Axis::Z => (
+                    [coords[0], coords[1], coords[4], coords[5], coords[2], coords[3]],
+                    [1, 1, 0, 0, 0, 0],
+                ),
+            },
+            (BlockOrientation::Facing, Some(TextureType::TopSideFrontActivatable(..))) => {
+                let front = coords[Facing::North.face_index()];
+                let side = coords[Facing::East.face_index()];
+                let top = coords[Facing::Up.face_index()];
+                match self.facing {
+                    Facing::Up | Facing::Down => {
+                        let mut oriented = [side; 6];
+                        oriented[self.facing.face_index()] = front;
+                        oriented[Facing::North.face_index()] = top;
+                        oriented[Facing::South.face_index()] = top;
+                        (oriented, [0; 6])
+                    }
+                    _ => {
+                        let turns = self.facing.horizontal_turns().unwrap();
+                        let mut oriented = coords;
+                        for (i, face) in HORIZONTAL_FACES.iter().enumerate() {
+                            oriented[HORIZONTAL_FACES[(i + turns) % 4]] = coords[*face];
+                        }
+                        (oriented, [0; 6])
+                    }
+                }
+            }
+            _ => (coords, [0; 6]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_state_round_trips_through_metadata() {
+        for facing in Facing::ALL {
+            for axis in [Axis::Y, Axis::X, Axis::Z] {
+                for active in [false, true] {
+                    for level in 0..=MAX_LEVEL {
+                        let state = BlockState { facing, axis, active, level };
+                        assert_eq!(BlockState::from_metadata(state.to_metadata()), state);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn level_is_clamped_to_max_level() {
+        let state = BlockState {
+            facing: Facing::West,
+            axis: Axis::Z,
+            active: true,
+            level: MAX_LEVEL + 5,
+        };
+        let loaded = BlockState::from_metadata(state.to_metadata());
+        assert_eq!(loaded.level, MAX_LEVEL);
+        assert_eq!((loaded.facing, loaded.axis, loaded.active), (Facing::West, Axis::Z, true));
+    }
+
+    #[test]
+    fn empty_metadata_is_the_default_state() {
+        assert_eq!(BlockState::default().to_metadata(), 0);
+    }
+}
@@ -3,7 +3,9 @@ use std::{collections::HashMap, slice::SliceIndex};
 use cgmath::Vector3;
 use json::{object, JsonValue};
 
-use super::{block::BLOCKS, save::save_chunk_data_to_json, BlockIndex, ChunkIndex};
+use super::{
+    block::BLOCKS, block_state::BlockState, save::save_chunk_data_to_json, BlockIndex, ChunkIndex,
+};
 
 pub(crate) const CHUNK_WIDTH: usize = 16;
 pub(crate) const CHUNK_HEIGHT: usize = 256;
@@ -14,15 +16,11 @@ pub(crate) type BlockDataArray<T> = [[[T; CHUNK_WIDTH]; CHUNK_HEIGHT]; CHUNK_WID
 pub enum ChunkUpdate {
     // 0: Index of chunk updated
     // 1: Index of block updated
-    // 2: New value of block at index
-    BlockUpdate(ChunkIndex, BlockIndex, usize),
+    BlockUpdate(ChunkIndex, BlockIndex),
 
-    // 0: Index of chunk updated
-    // 1: Index of neighbor chunk updated
-    // 2: Index of block that triggered the update
-    // 3: Index of neighbor affected by the update
-    // 4: value of block that triggered the update 
-    NeighborChanged(ChunkIndex, ChunkIndex, BlockIndex, BlockIndex, usize),
+    // 0: Index of neighbor chunk updated
+    // 1: Index of neighbor affected by the update
+    NeighborChanged(ChunkIndex, BlockIndex),
 }
 
 #[derive(Clone)]
@@ -55,14 +53,38 @@ impl Chunk {
     pub fn set_block(&mut self, block_index: &BlockIndex, block_id: usize) -> usize {
         let prev_block_id = self.blocks[block_index.x][block_index.y][block_index.z];
         self.blocks[block_index.x][block_index.y][block_index.z] = block_id;
+        if prev_block_id != block_id {
+            // State belongs to the old block and would be meaningless for the new one
+            self.metadata[block_index.x][block_index.y][block_index.z] = 0;
+        }
         self.needs_mesh_rebuild = true;
         prev_block_id
     }
 
+    pub fn set_block_with_state(
+        &mut self,
+        block_index: &BlockIndex,
+        block_id: usize,
+        state: BlockState,
+    ) -> usize {
+        let prev_block_id = self.set_block(block_index, block_id);
+        self.set_block_state(block_index, state);
+        prev_block_id
+    }
+
     pub fn get_metadata(&self, block_index: &BlockIndex) -> usize {
         self.metadata[block_index.x][block_index.y][block_index.z]
     }
 
+    pub fn get_block_state(&self, block_index: &BlockIndex) -> BlockState {
+        BlockState::from_metadata(self.get_metadata(block_index))
+    }
+
+    pub fn set_block_state(&mut self, block_index: &BlockIndex, state: BlockState) {
+        self.metadata[block_index.x][block_index.y][block_index.z] = state.to_metadata();
+        self.needs_mesh_rebuild = true;
+    }
+
     /*pub fn update_lighting(&mut self, pending_lights: Vec<(BlockIndex, usize)>) -> HashMap<ChunkIndex, Vec<(BlockIndex, usize)>>{
         // Reset lighting
         for x in 0..CHUNK_WIDTH {
@@ -235,6 +257,16 @@ impl Chunk {
             }
         }
 
+        // Saves from before block states were introduced have no metadata
+        let chunk_metadata = &chunk_json["metadata"];
+        for (x, row) in chunk_metadata.members().enumerate() {
+            for (y, column) in row.members().enumerate() {
+                for (z, metadata) in column.members().enumerate() {
+                    chunk.metadata[x][y][z] = metadata.as_usize().unwrap();
+                }
+            }
+        }
+
         let chunk_lighting = &chunk_json["lighting"];
         for (x, row) in chunk_lighting.members().enumerate() {
             for (y, column) in row.members().enumerate() {
@@ -251,7 +283,62 @@ impl Chunk {
     pub fn to_json_array(&self) -> JsonValue {
         object! {
             "blocks": save_chunk_data_to_json(&self.blocks),
+            "metadata": save_chunk_data_to_json(&self.metadata),
             "lighting": save_chunk_data_to_json(&self.lighting)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::block_state::{Axis, Facing};
+
+    /// Chunks are built on the stack before being boxed, which is more than a test thread's stack
+    /// holds in a debug build
+    fn with_large_stack(test: fn()) {
+        std::thread::Builder::new()
+            .stack_size(32 * 1024 * 1024)
+            .spawn(test)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    fn oriented_state() -> BlockState {
+        BlockState {
+            facing: Facing::East,
+            axis: Axis::X,
+            active: true,
+            level: 3,
+        }
+    }
+
+    #[test]
+    fn block_states_are_saved_with_the_chunk() {
+        with_large_stack(|| {
+            let mut chunk = Chunk::new();
+            let block_index = BlockIndex::new(3, 70, 12);
+            chunk.set_block_with_state(&block_index, 1, oriented_state());
+
+            let loaded = Chunk::from_json_array(&chunk.to_json_array());
+            assert_eq!(loaded.get_block(&block_index), 1);
+            assert_eq!(loaded.get_block_state(&block_index), oriented_state());
+        });
+    }
+
+    #[test]
+    fn chunks_saved_without_metadata_load_with_default_states() {
+        with_large_stack(|| {
+            let mut chunk = Chunk::new();
+            let block_index = BlockIndex::new(3, 70, 12);
+            chunk.set_block_with_state(&block_index, 1, oriented_state());
+
+            let mut chunk_json = chunk.to_json_array();
+            chunk_json.remove("metadata");
+            let loaded = Chunk::from_json_array(&chunk_json);
+            assert_eq!(loaded.get_block(&block_index), 1);
+            assert_eq!(loaded.get_block_state(&block_index), BlockState::default());
+        });
+    }
+}
@@ -8,7 +8,11 @@ use super::{
     chunk::{Chunk, CHUNK_WIDTH}, BlockWorldPos, ChunkIndex, ChunkListTrait, Terrain
 };
 
+/// Seed for worlds created without one
+pub const DEFAULT_SEED: u32 = 1;
+
 pub struct TerrainGenConfig {
+    seed: u32,
     perlin: Perlin,
     continentalness_scale: Vector2<f64>,
     continentalness_spline: Spline<f64, f64>,
@@ -42,7 +46,8 @@ impl Default for TerrainGenConfig {
         ];
 
         Self {
-            perlin: Perlin::new(1),
+            seed: DEFAULT_SEED,
+            perlin: Perlin::new(DEFAULT_SEED),
             continentalness_scale: Vector2::new(0.002, 0.002),
             continentalness_spline: splines::Spline::from_vec(cont_keys),
 
@@ -56,14 +61,38 @@ impl Default for TerrainGenConfig {
 
 #[allow(unused)]
 #[derive(PartialEq, Eq, Clone, Copy)]
-enum Biome {
+pub(crate) enum Biome {
     Plains,
     Hills,
     Forest,
     Desert,
 }
 
+impl Biome {
+    pub fn name(self) -> &'static str {
+        match self {
+            Biome::Plains => "plains",
+            Biome::Hills => "hills",
+            Biome::Forest => "forest",
+            Biome::Desert => "desert",
+        }
+    }
+}
+
 impl TerrainGenConfig {
+    /// Generation settings for the world grown from `seed`
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            seed,
+            perlin: Perlin::new(seed),
+            ..Self::default()
+        }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
     pub(crate) fn get_perlin(&self, offset: [f64; 2]) -> f64 {
         self.perlin.get(offset)
     }
@@ -78,7 +107,7 @@ impl TerrainGenConfig {
             .unwrap_or(0.0)
     }
 
-    fn get_biome(&self, offset: [f64; 2]) -> Biome {
+    pub(crate) fn get_biome(&self, offset: [f64; 2]) -> Biome {
         let cont_index = self
             .cont_map_spline
             .sample(self.get_continentalness(offset))
@@ -0,0 +1,206 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use cgmath::Vector3;
+use json::JsonValue;
+
+use crate::physics::collision::Rect3;
+
+use super::{
+    block::{Block, BlockOrientation},
+    block_state::{BlockState, HORIZONTAL_FACES},
+    BlockWorldPos,
+};
+
+const BLOCK_MODELS_JSON: &str = include_str!("../../assets/models/block_models.json");
+
+/// Model coordinates and UVs are given in texels, 16 to a block
+const TEXELS_PER_BLOCK: f32 = 16.0;
+
+/// JSON face names, in the mesher's face order
+const FACE_NAMES: [&str; 6] = ["east", "west", "up", "down", "south", "north"];
+
+/// The (axis, flipped) pairs a face's U and V coordinates run along, matching the cube face templates
+const FACE_UV_AXES: [((usize, bool), (usize, bool)); 6] = [
+    ((2, false), (1, false)),
+    ((2, true), (1, false)),
+    ((0, false), (2, false)),
+    ((0, false), (2, false)),
+    ((0, false), (1, false)),
+    ((0, false), (1, false)),
+];
+
+static BLOCK_MODELS: OnceLock<HashMap<String, BlockModel>> = OnceLock::new();
+
+#[derive(Clone, Copy)]
+pub struct ModelFace {
+    /// Area of the block's atlas tile as (u0, v0, u1, v1), projected from the box when unset
+    pub uv: Option<[f32; 4]>,
+}
+
+/// An axis-aligned box within the unit block, with the faces which should be drawn
+#[derive(Clone, Copy)]
+pub struct ModelBox {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+    pub faces: [Option<ModelFace>; 6],
+}
+
+impl ModelBox {
+    pub const FULL: ModelBox = ModelBox {
+        min: Vector3::new(0.0, 0.0, 0.0),
+        max: Vector3::new(1.0, 1.0, 1.0),
+        faces: [Some(ModelFace { uv: None }); 6],
+    };
+
+    fn from_json(model_box: &JsonValue) -> Self {
+        let corner = |value: &JsonValue| {
+            Vector3::new(
+                value[0].as_f32().unwrap() / TEXELS_PER_BLOCK,
+                value[1].as_f32().unwrap() / TEXELS_PER_BLOCK,
+                value[2].as_f32().unwrap() / TEXELS_PER_BLOCK,
+            )
+        };
+
+        let mut faces = [Some(ModelFace { uv: None }); 6];
+        if !model_box["faces"].is_null() {
+            for (i, name) in FACE_NAMES.iter().enumerate() {
+                let face = &model_box["faces"][*name];
+                faces[i] = if face.is_null() {
+                    None
+                } else if face["uv"].is_null() {
+                    Some(ModelFace { uv: None })
+                } else {
+                    let mut uv = [0.0; 4];
+                    for (j, value) in uv.iter_mut().enumerate() {
+                        *value = face["uv"][j].as_f32().unwrap() / TEXELS_PER_BLOCK;
+                    }
+                    Some(ModelFace { uv: Some(uv) })
+                };
+            }
+        }
+
+        ModelBox {
+            min: corner(&model_box["from"]),
+            max: corner(&model_box["to"]),
+            faces,
+        }
+    }
+
+    /// Texture area for `face`, or `None` if the face isn't drawn
+    pub fn face_uv(&self, face: usize) -> Option<[f32; 4]> {
+        self.faces[face].map(|model_face| {
+            model_face.uv.unwrap_or_else(|| {
+                let ((u_axis, u_flipped), (v_axis, v_flipped)) = FACE_UV_AXES[face];
+                let project = |axis: usize, flipped: bool| {
+                    if flipped {
+                        (1.0 - self.max[axis], 1.0 - self.min[axis])
+                    } else {
+                        (self.min[axis], self.max[axis])
+                    }
+                };
+                let (u0, u1) = project(u_axis, u_flipped);
+                let (v0, v1) = project(v_axis, v_flipped);
+                [u0, v0, u1, v1]
+            })
+        })
+    }
+
+    /// Whether `face` of this box lies on the block's boundary
+    pub fn touches_face(&self, face: usize) -> bool {
+        let axis = face / 2;
+        if face.is_multiple_of(2) {
+            self.max[axis] >= 1.0
+        } else {
+            self.min[axis] <= 0.0
+        }
+    }
+
+    /// Whether this box fills the whole of the block's `face`
+    pub fn covers_face(&self, face: usize) -> bool {
+        self.touches_face(face)
+            && (0..3)
+                .filter(|axis| *axis != face / 2)
+                .all(|axis| self.min[axis] <= 0.0 && self.max[axis] >= 1.0)
+    }
+
+    pub fn world_rect(&self, world_pos: &BlockWorldPos) -> Rect3 {
+        Rect3::new(
+            Vector3::new(world_pos.x as f32, world_pos.y as f32, world_pos.z as f32) + self.min,
+            self.max - self.min,
+        )
+    }
+
+    /// Rotate the box clockwise around the block's vertical center line
+    fn rotated(&self, turns: usize) -> Self {
+        let mut rotated = *self;
+        for _ in 0..turns % 4 {
+            let previous = rotated;
+            rotated.min.x = 1.0 - previous.max.z;
+            rotated.max.x = 1.0 - previous.min.z;
+            rotated.min.z = previous.min.x;
+            rotated.max.z = previous.max.x;
+            for (i, face) in HORIZONTAL_FACES.iter().enumerate() {
+                rotated.faces[HORIZONTAL_FACES[(i + 1) % 4]] = previous.faces[*face];
+            }
+        }
+        rotated
+    }
+}
+
+/// A block shape made of boxes, modelled facing north
+pub struct BlockModel {
+    boxes: Vec<ModelBox>,
+    collision: Vec<ModelBox>,
+}
+
+impl BlockModel {
+    fn from_json(model: &JsonValue) -> Self {
+        let boxes: Vec<ModelBox> = model["boxes"].members().map(ModelBox::from_json).collect();
+        let collision = if model["collision"].is_null() {
+            boxes.clone()
+        } else {
+            model["collision"]
+                .members()
+                .map(ModelBox::from_json)
+                .collect()
+        };
+        BlockModel { boxes, collision }
+    }
+
+    fn orient(block: &Block, state: &BlockState, boxes: &[ModelBox]) -> Vec<ModelBox> {
+        let turns = match block.orientation {
+            BlockOrientation::Facing => state.facing.horizontal_turns().unwrap_or(0),
+            _ => 0,
+        };
+        boxes
+            .iter()
+            .map(|model_box| model_box.rotated(turns))
+            .collect()
+    }
+
+    /// Boxes to draw for `block` in `state`
+    pub fn boxes(&self, block: &Block, state: &BlockState) -> Vec<ModelBox> {
+        Self::orient(block, state, &self.boxes)
+    }
+
+    /// Boxes which entities collide with for `block` in `state`
+    pub fn collision_boxes(&self, block: &Block, state: &BlockState) -> Vec<ModelBox> {
+        Self::orient(block, state, &self.collision)
+    }
+}
+
+fn load_block_models(models_json: &'static str) -> HashMap<String, BlockModel> {
+    let models = json::parse(models_json).unwrap();
+    models
+        .entries()
+        .map(|(name, model)| (name.to_string(), BlockModel::from_json(model)))
+        .collect()
+}
+
+/// Fetch the model called `name`, loading the block model definitions on first use
+pub fn block_model(name: &str) -> &'static BlockModel {
+    BLOCK_MODELS
+        .get_or_init(|| load_block_models(BLOCK_MODELS_JSON))
+        .get(name)
+        .unwrap_or_else(|| panic!("Block model \"{}\" is not defined", name))
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::{Arc, RwLock}};
 use json::{object, JsonValue};
 
 use super::{
-    chunk::{BlockDataArray, Chunk, CHUNK_HEIGHT, CHUNK_WIDTH}, generation::TerrainGenConfig, ChunkIndex, Terrain
+    chunk::{BlockDataArray, Chunk, CHUNK_HEIGHT, CHUNK_WIDTH}, generation::TerrainGenConfig, tick::BlockUpdateScheduler, ChunkIndex, Terrain, DEFAULT_LOADING_RADIUS
 };
 
 pub(crate) fn save_chunk_data_to_json<T: Clone + std::convert::Into<json::JsonValue>>(
@@ -36,7 +36,9 @@ impl Terrain {
             block_placement_queue: HashMap::new(),
             chunk_update_queue: Vec::new(),
             event_queue: Vec::new(),
-            config
+            block_updates: BlockUpdateScheduler::new(),
+            config,
+            loading_radius: DEFAULT_LOADING_RADIUS,
         }
     }
 
@@ -52,6 +54,7 @@ impl Terrain {
         }
 
         object! {
+            "seed" : self.config.seed(),
             "chunks" : chunks,
         }
     }
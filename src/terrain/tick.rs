@@ -0,0 +1,308 @@
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use super::{
+    block::{block_index_by_name, TickBehaviour, BLOCKS},
+    block_state::BlockState,
+    chunk::{ChunkUpdate, CHUNK_HEIGHT, CHUNK_WIDTH},
+    BlockIndex, BlockWorldPos, ChunkIndex, ChunkListTrait, Terrain,
+};
+
+/// Block ticks run at a fixed rate, independent of how often the game loop calls `Terrain::tick`
+pub(crate) const BLOCK_TICK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Chunks are split into sections of this height for random ticking
+pub(crate) const SECTION_HEIGHT: usize = 16;
+const RANDOM_TICKS_PER_SECTION: usize = 3;
+
+/// Upper bound on neighbor updates handled per tick, leftovers carry over to the next tick
+const MAX_UPDATES_PER_TICK: usize = 4096;
+
+const LEAF_DECAY_DISTANCE: usize = 4;
+const LEAF_DECAY_DELAY: u64 = 20;
+const FALLING_BLOCK_DELAY: u64 = 2;
+
+const NEIGHBOR_OFFSETS: [BlockWorldPos; 6] = [
+    BlockWorldPos::new(1, 0, 0),
+    BlockWorldPos::new(-1, 0, 0),
+    BlockWorldPos::new(0, 1, 0),
+    BlockWorldPos::new(0, -1, 0),
+    BlockWorldPos::new(0, 0, 1),
+    BlockWorldPos::new(0, 0, -1),
+];
+
+pub(crate) struct BlockUpdateScheduler {
+    current_tick: u64,
+    last_tick: Instant,
+
+    // Scheduled ticks keyed by the tick they are due on
+    scheduled: BTreeMap<u64, Vec<BlockWorldPos>>,
+    pending_updates: VecDeque<ChunkUpdate>,
+
+    // Blocks which lost their support and should be spawned as falling entities
+    falling_blocks: Vec<(BlockWorldPos, usize)>,
+}
+
+impl BlockUpdateScheduler {
+    pub fn new() -> Self {
+        Self {
+            current_tick: 0,
+            last_tick: Instant::now(),
+            scheduled: BTreeMap::new(),
+            pending_updates: VecDeque::new(),
+            falling_blocks: Vec::new(),
+        }
+    }
+
+    /// Request a scheduled tick for the block at `world_pos` after `delay` block ticks
+    pub fn schedule(&mut self, world_pos: BlockWorldPos, delay: u64) {
+        let due = self.current_tick + delay.max(1);
+        let due_blocks = self.scheduled.entry(due).or_default();
+        if !due_blocks.contains(&world_pos) {
+            due_blocks.push(world_pos);
+        }
+    }
+
+    pub fn push_update(&mut self, update: ChunkUpdate) {
+        self.pending_updates.push_back(update);
+    }
+
+    fn take_due(&mut self) -> Vec<BlockWorldPos> {
+        let not_due = self.scheduled.split_off(&(self.current_tick + 1));
+        let due = std::mem::replace(&mut self.scheduled, not_due);
+        due.into_values().flatten().collect()
+    }
+}
+
+impl Terrain {
+    /// Convert a chunk index and the block index within it back to world coordinates
+    pub fn world_pos(chunk_index: &ChunkIndex, block_index: &BlockIndex) -> BlockWorldPos {
+        BlockWorldPos::new(
+            chunk_index.x * CHUNK_WIDTH as isize + block_index.x as isize,
+            block_index.y as isize,
+            chunk_index.y * CHUNK_WIDTH as isize + block_index.z as isize,
+        )
+    }
+
+    /// Set the block at `world_pos` and notify its neighbors if the block changed
+    pub(crate) fn set_block_at_world_pos(&mut self, world_pos: &BlockWorldPos, block_id: usize) -> usize {
+        self.set_block_with_state_at_world_pos(world_pos, block_id, BlockState::default())
+    }
+
+    pub(crate) fn set_block_with_state_at_world_pos(
+        &mut self,
+        world_pos: &BlockWorldPos,
+        block_id: usize,
+        state: BlockState,
+    ) -> usize {
+        if let Some((chunk_index, block_index)) = Terrain::chunk_and_block_index(world_pos) {
+            if let Some(chunk) = self.chunks.at_index_mut(&chunk_index) {
+                let prev_block_id =
+                    chunk.write().unwrap().set_block_with_state(&block_index, block_id, state);
+                if prev_block_id != block_id {
+                    self.block_updates.push_update(ChunkUpdate::BlockUpdate(chunk_index, block_index));
+                }
+                return prev_block_id;
+            }
+        }
+        0
+    }
+
+    pub(crate) fn schedule_block_tick(&mut self, world_pos: BlockWorldPos, delay: u64) {
+        self.block_updates.schedule(world_pos, delay);
+    }
+
+    /// Drain the blocks which started falling since the last call
+    pub fn take_falling_blocks(&mut self) -> Vec<(BlockWorldPos, usize)> {
+        std::mem::take(&mut self.block_updates.falling_blocks)
+    }
+
+    pub(crate) fn tick_blocks(&mut self) {
+        if self.block_updates.last_tick.elapsed() < BLOCK_TICK_INTERVAL {
+            return;
+        }
+        self.block_updates.last_tick = Instant::now();
+        self.block_updates.current_tick += 1;
+
+        for world_pos in self.block_updates.take_due() {
+            self.scheduled_tick(&world_pos);
+        }
+        self.process_block_updates();
+        self.random_ticks();
+    }
+
+    fn process_block_updates(&mut self) {
+        for _ in 0..MAX_UPDATES_PER_TICK {
+            let update = match self.block_updates.pending_updates.pop_front() {
+                Some(update) => update,
+                None => break,
+            };
+            match update {
+                ChunkUpdate::BlockUpdate(chunk_index, block_index) => {
+                    let world_pos = Terrain::world_pos(&chunk_index, &block_index);
+                    for offset in NEIGHBOR_OFFSETS {
+                        if let Some((neighbor_chunk, neighbor_block)) =
+                            Terrain::chunk_and_block_index(&(world_pos + offset))
+                        {
+                            self.block_updates
+                                .push_update(ChunkUpdate::NeighborChanged(neighbor_chunk, neighbor_block));
+                        }
+                    }
+                }
+                ChunkUpdate::NeighborChanged(neighbor_chunk, neighbor_block) => {
+                    let world_pos = Terrain::world_pos(&neighbor_chunk, &neighbor_block);
+                    self.neighbor_changed(&world_pos);
+                }
+            }
+        }
+    }
+
+    fn random_ticks(&mut self) {
+        let mut tick_queue = Vec::new();
+        for (chunk_index, chunk) in self.chunks[0].iter() {
+            let chunk = chunk.read().unwrap();
+            for section in 0..CHUNK_HEIGHT / SECTION_HEIGHT {
+                for _ in 0..RANDOM_TICKS_PER_SECTION {
+                    let block_index = BlockIndex::new(
+                        rand::random::<usize>() % CHUNK_WIDTH,
+                        section * SECTION_HEIGHT + rand::random::<usize>() % SECTION_HEIGHT,
+                        rand::random::<usize>() % CHUNK_WIDTH,
+                    );
+                    if BLOCKS[chunk.get_block(&block_index)].tick_behaviour != TickBehaviour::None {
+                        tick_queue.push(Terrain::world_pos(chunk_index, &block_index));
+                    }
+                }
+            }
+        }
+
+        for world_pos in tick_queue {
+            self.random_tick(&world_pos);
+        }
+    }
+
+    fn random_tick(&mut self, world_pos: &BlockWorldPos) {
+        match BLOCKS[self.block_at_world_pos(world_pos)].tick_behaviour {
+            TickBehaviour::Grass => self.spread_grass(world_pos),
+            TickBehaviour::Plant => self.check_plant_support(world_pos),
+            TickBehaviour::Leaves => self.decay_leaves(world_pos),
+            TickBehaviour::Falling => self.schedule_block_tick(*world_pos, FALLING_BLOCK_DELAY),
+            TickBehaviour::None => {}
+        }
+    }
+
+    fn scheduled_tick(&mut self, world_pos: &BlockWorldPos) {
+        match BLOCKS[self.block_at_world_pos(world_pos)].tick_behaviour {
+            TickBehaviour::Plant => self.check_plant_support(world_pos),
+            TickBehaviour::Leaves => self.decay_leaves(world_pos),
+            TickBehaviour::Falling => self.check_falling_block(world_pos),
+            TickBehaviour::Grass | TickBehaviour::None => {}
+        }
+    }
+
+    fn neighbor_changed(&mut self, world_pos: &BlockWorldPos) {
+        match BLOCKS[self.block_at_world_pos(world_pos)].tick_behaviour {
+            TickBehaviour::Plant => self.check_plant_support(world_pos),
+            TickBehaviour::Leaves => {
+                // Stagger decay so a felled tree disappears gradually
+                let delay = LEAF_DECAY_DELAY + rand::random::<u64>() % LEAF_DECAY_DELAY;
+                self.schedule_block_tick(*world_pos, delay);
+            }
+            TickBehaviour::Falling => self.schedule_block_tick(*world_pos, FALLING_BLOCK_DELAY),
+            TickBehaviour::Grass | TickBehaviour::None => {}
+        }
+    }
+
+    /// Grass turns to dirt when covered, otherwise it spreads onto nearby uncovered dirt
+    fn spread_grass(&mut self, world_pos: &BlockWorldPos) {
+        let dirt = block_index_by_name("Dirt");
+        let grass = block_index_by_name("Grass");
+
+        let above = world_pos + BlockWorldPos::new(0, 1, 0);
+        if !BLOCKS[self.block_at_world_pos(&above)].transparent {
+            self.set_block_at_world_pos(world_pos, dirt);
+            return;
+        }
+
+        let target = world_pos
+            + BlockWorldPos::new(
+                (rand::random::<usize>() % 3) as isize - 1,
+                (rand::random::<usize>() % 5) as isize - 3,
+                (rand::random::<usize>() % 3) as isize - 1,
+            );
+        let target_above = target + BlockWorldPos::new(0, 1, 0);
+        if self.block_at_world_pos(&target) == dirt
+            && BLOCKS[self.block_at_world_pos(&target_above)].transparent
+        {
+            self.set_block_at_world_pos(&target, grass);
+        }
+    }
+
+    /// Plants break when the block below them is no longer soil
+    fn check_plant_support(&mut self, world_pos: &BlockWorldPos) {
+        let below = self.block_at_world_pos(&(world_pos - BlockWorldPos::new(0, 1, 0)));
+        if below != block_index_by_name("Grass") && below != block_index_by_name("Dirt") {
+            self.set_block_at_world_pos(world_pos, 0);
+        }
+    }
+
+    /// Leaves decay when no log can be reached through other leaves within `LEAF_DECAY_DISTANCE`
+    fn decay_leaves(&mut self, world_pos: &BlockWorldPos) {
+        let log = block_index_by_name("Oak Log");
+
+        let mut visited = HashSet::new();
+        let mut frontier = VecDeque::new();
+        visited.insert(*world_pos);
+        frontier.push_back((*world_pos, 0));
+        while let Some((pos, distance)) = frontier.pop_front() {
+            for offset in NEIGHBOR_OFFSETS {
+                let neighbor = pos + offset;
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let loaded = Terrain::chunk_and_block_index(&neighbor)
+                    .map(|(chunk_index, _)| self.chunks.at_index(&chunk_index).is_some())
+                    .unwrap_or(false);
+                if !loaded {
+                    // The tree may continue into a chunk which hasn't been generated yet
+                    return;
+                }
+                let block_id = self.block_at_world_pos(&neighbor);
+                if block_id == log {
+                    return;
+                }
+                if BLOCKS[block_id].tick_behaviour == TickBehaviour::Leaves
+                    && distance + 1 < LEAF_DECAY_DISTANCE
+                {
+                    frontier.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+
+        self.set_block_at_world_pos(world_pos, 0);
+    }
+
+    /// Gravity-affected blocks without solid support are removed and handed off as falling entities
+    fn check_falling_block(&mut self, world_pos: &BlockWorldPos) {
+        let below = world_pos - BlockWorldPos::new(0, 1, 0);
+        if below.y < 0 || self.solid_block_at_world_pos(&below) {
+            return;
+        }
+        let block_id = self.set_block_at_world_pos(world_pos, 0);
+        if block_id != 0 {
+            self.block_updates.falling_blocks.push((*world_pos, block_id));
+        }
+    }
+
+    /// Turn a landed falling block back into a block, stacking it on top of anything already there
+    pub fn land_falling_block(&mut self, world_pos: &BlockWorldPos, block_id: usize) {
+        let mut landing_pos = *world_pos;
+        while self.solid_block_at_world_pos(&landing_pos) {
+            landing_pos.y += 1;
+        }
+        if Terrain::chunk_and_block_index(&landing_pos).is_some() {
+            self.set_block_at_world_pos(&landing_pos, block_id);
+        }
+    }
+}